@@ -0,0 +1,53 @@
+#[macro_use]
+extern crate criterion;
+#[macro_use]
+extern crate refraction;
+
+use criterion::{BenchmarkId, Criterion, black_box};
+use refraction::PartialLens;
+
+type D1 = (u64, u64);
+type D2 = (D1, u64);
+type D3 = (D2, u64);
+type D4 = (D3, u64);
+type D5 = (D4, u64);
+
+fn bench_composed<L>(c: &mut Criterion, depth: usize, l: L, v: L::InitialSource)
+    where L: PartialLens<InitialTarget = u64, FinalTarget = u64>,
+          L::InitialSource: Clone,
+          L::AtInitial: PartialLens,
+          L::AtFinal: PartialLens
+{
+    c.bench_with_input(BenchmarkId::new("modify", depth), &v, |b, v| {
+        b.iter(|| l.modify(black_box(v.clone()), |x| x + 1))
+    });
+    c.bench_with_input(BenchmarkId::new("try_get_context", depth), &v, |b, v| {
+        b.iter(|| {
+            let (x, ctx) = l.try_get_context(black_box(v.clone())).ok().unwrap();
+            l.fill_context(ctx, x + 1)
+        })
+    });
+    c.bench_with_input(BenchmarkId::new("try_get_inject", depth), &v, |b, v| {
+        b.iter(|| {
+            let (x, mut inj) = l.try_get_inject(black_box(v.clone())).ok().unwrap();
+            inj(x + 1)
+        })
+    });
+}
+
+fn composed(c: &mut Criterion) {
+    let l1 = || field_lens!(D1 => 0: u64);
+    let l2 = || field_lens!(D2 => 0: D1);
+    let l3 = || field_lens!(D3 => 0: D2);
+    let l4 = || field_lens!(D4 => 0: D3);
+    let l5 = || field_lens!(D5 => 0: D4);
+    let v1: D1 = (1, 0);
+    let v3: D3 = ((v1, 0), 0);
+    let v5: D5 = ((v3, 0), 0);
+    bench_composed(c, 1, l1(), v1);
+    bench_composed(c, 3, chain_compose!(l1(), l2(), l3()), v3);
+    bench_composed(c, 5, chain_compose!(l1(), l2(), l3(), l4(), l5()), v5);
+}
+
+criterion_group!(benches, composed);
+criterion_main!(benches);
@@ -0,0 +1,220 @@
+use proc_macro2::{Group, TokenStream, TokenTree};
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Error, GenericParam, Ident, Result, WherePredicate, parse_quote};
+use super::{impl_via, members, nested_visibility, phantom_struct};
+
+/// Rename every occurrence of the identifiers in `from` to the corresponding
+/// identifier in `to`.
+fn substitute(tokens: TokenStream, from: &[Ident], to: &[Ident]) -> TokenStream {
+    tokens.into_iter()
+          .map(|tt| match tt {
+              TokenTree::Ident(ref id) if from.contains(id) => {
+                  let i = from.iter().position(|f| f == id).unwrap();
+                  TokenTree::Ident(to[i].clone())
+              }
+              TokenTree::Group(g) => {
+                  let mut h = Group::new(g.delimiter(), substitute(g.stream(), from, to));
+                  h.set_span(g.span());
+                  TokenTree::Group(h)
+              }
+              tt => tt,
+          })
+          .collect()
+}
+
+/// Whether `tokens` mention any of `ids`.
+fn mentions(tokens: TokenStream, ids: &[Ident]) -> bool {
+    tokens.into_iter().any(|tt| match tt {
+        TokenTree::Ident(ref id) => ids.contains(id),
+        TokenTree::Group(g) => mentions(g.stream(), ids),
+        _ => false,
+    })
+}
+
+/// `ty`, applied to `args` if there are any.
+fn applied(ty: &Ident, args: &[TokenStream]) -> TokenStream {
+    if args.is_empty() {
+        quote!(#ty)
+    } else {
+        quote!(#ty<#(#args),*>)
+    }
+}
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let fields = match input.data {
+        Data::Struct(ref data) if data.fields.len() == 1 => &data.fields,
+        _ => {
+            return Err(Error::new_spanned(&input.ident,
+                                          "`Iso` can only be derived for structs with exactly one \
+                                           field"))
+        }
+    };
+    let field = fields.iter().next().unwrap();
+    let name = &input.ident;
+    let member = &members(fields)[0];
+    let target = &field.ty;
+    let iso = format_ident!("{}Iso", name);
+    let vis = nested_visibility(&input.vis, &field.vis);
+    let doc = format!("An isomorphism between `{}` and the value it wraps.", name);
+
+    // Each type parameter that the field mentions may change across the
+    // isomorphism, and so gets a counterpart standing for its final type.
+    let initials: Vec<Ident> = input.generics
+                                    .type_params()
+                                    .filter(|p| mentions(quote!(#target), ::std::slice::from_ref(&p.ident)))
+                                    .map(|p| p.ident.clone())
+                                    .collect();
+    let finals: Vec<Ident> = initials.iter().map(|id| format_ident!("__{}Final", id)).collect();
+    let subst = |tokens: TokenStream| substitute(tokens, &initials, &finals);
+
+    let args: Vec<TokenStream> = input.generics
+                                      .params
+                                      .iter()
+                                      .map(|param| match *param {
+                                          GenericParam::Type(ref p) => {
+                                              let id = &p.ident;
+                                              quote!(#id)
+                                          }
+                                          GenericParam::Lifetime(ref p) => {
+                                              let lt = &p.lifetime;
+                                              quote!(#lt)
+                                          }
+                                          GenericParam::Const(ref p) => {
+                                              let id = &p.ident;
+                                              quote!(#id)
+                                          }
+                                      })
+                                      .collect();
+    let final_args: Vec<TokenStream> = args.iter().map(|a| subst(a.clone())).collect();
+    let source = applied(name, &args);
+    let final_source = applied(name, &final_args);
+    let final_target = subst(quote!(#target));
+
+    let mut generics = input.generics.clone();
+    for (initial, fin) in initials.iter().zip(&finals) {
+        let bounds = &input.generics.type_params().find(|p| p.ident == *initial).unwrap().bounds;
+        generics.params.push(if bounds.is_empty() {
+            parse_quote!(#fin = #initial)
+        } else {
+            let bounds = subst(quote!(#bounds));
+            parse_quote!(#fin: #bounds = #initial)
+        });
+    }
+    if let Some(ref where_clause) = input.generics.where_clause {
+        let extra: Vec<WherePredicate> = where_clause.predicates
+                                                     .iter()
+                                                     .filter(|w| mentions(quote!(#w), &initials))
+                                                     .map(|w| {
+                                                         let w = subst(quote!(#w));
+                                                         parse_quote!(#w)
+                                                     })
+                                                     .collect();
+        generics.make_where_clause().predicates.extend(extra);
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let (orig_impl_generics, orig_ty_generics, orig_where_clause) =
+        input.generics.split_for_impl();
+    let at_initial = applied(&iso,
+                             &args.iter()
+                                  .cloned()
+                                  .chain(initials.iter().map(|id| quote!(#id)))
+                                  .collect::<Vec<_>>());
+    let at_final = applied(&iso,
+                           &final_args.iter()
+                                      .cloned()
+                                      .chain(finals.iter().map(|id| quote!(#id)))
+                                      .collect::<Vec<_>>());
+
+    let mut out = phantom_struct(&iso, vis, &generics, &source, &final_source, &doc);
+    out.extend(impl_via("impl_traversal_via_partial_lens", &iso, &generics));
+    out.extend(impl_via("impl_review_via_prism", &iso, &generics));
+    out.extend(impl_via("impl_getter_via_lens", &iso, &generics));
+    out.extend(quote! {
+        impl #impl_generics ::refraction::Lenticuloid for #iso #ty_generics #where_clause {
+            type InitialSource = #source;
+            type InitialTarget = #target;
+            type FinalSource = #final_source;
+            type FinalTarget = #final_target;
+            type AtInitial = #at_initial;
+            #[inline]
+            fn at_initial(&self) -> Self::AtInitial {
+                #iso::mk()
+            }
+            type AtFinal = #at_final;
+            #[inline]
+            fn at_final(&self) -> Self::AtFinal {
+                #iso::mk()
+            }
+        }
+
+        impl #impl_generics ::refraction::PartialLens for #iso #ty_generics #where_clause {
+            type Context = ();
+            #[inline]
+            fn try_get(&self, v: Self::InitialSource) ->
+                ::std::result::Result<Self::InitialTarget, Self::FinalSource>
+            {
+                ::std::result::Result::Ok(v.#member)
+            }
+            #[inline]
+            fn try_get_context(&self, v: Self::InitialSource) ->
+                ::std::result::Result<(Self::InitialTarget, ()), Self::FinalSource>
+            {
+                ::std::result::Result::Ok((v.#member, ()))
+            }
+            #[inline]
+            fn fill_context(&self, _c: (), x: Self::FinalTarget) -> Self::FinalSource {
+                #name { #member: x }
+            }
+            #[inline]
+            fn set(&self, _v: Self::InitialSource, x: Self::FinalTarget) -> Self::FinalSource {
+                #name { #member: x }
+            }
+            #[inline]
+            fn exchange(&self, v: Self::InitialSource, x: Self::FinalTarget) ->
+                (::std::option::Option<Self::InitialTarget>, Self::FinalSource)
+            {
+                (::std::option::Option::Some(v.#member), #name { #member: x })
+            }
+            #[inline]
+            fn modify<__F>(&self, v: Self::InitialSource, f: __F) -> Self::FinalSource
+                where __F: FnOnce(Self::InitialTarget) -> Self::FinalTarget
+            {
+                #name { #member: f(v.#member) }
+            }
+            #[inline]
+            fn modify_with<__F, __X>(&self, v: Self::InitialSource, f: __F) ->
+                (Self::FinalSource, ::std::option::Option<__X>)
+                where __F: FnOnce(Self::InitialTarget) -> (Self::FinalTarget, __X)
+            {
+                let (x, aux) = f(v.#member);
+                (#name { #member: x }, ::std::option::Option::Some(aux))
+            }
+        }
+
+
+        impl #impl_generics ::refraction::Lens for #iso #ty_generics #where_clause {
+            #[inline]
+            fn get(&self, v: Self::InitialSource) -> Self::InitialTarget {
+                v.#member
+            }
+        }
+
+
+        impl #impl_generics ::refraction::Prism for #iso #ty_generics #where_clause {
+            #[inline]
+            fn inject(&self, v: Self::FinalTarget) -> Self::FinalSource {
+                #name { #member: v }
+            }
+        }
+
+        impl #impl_generics ::refraction::Iso for #iso #ty_generics #where_clause {}
+
+        impl #orig_impl_generics #name #orig_ty_generics #orig_where_clause {
+            #[inline]
+            #vis fn iso() -> #at_initial {
+                #iso::mk()
+            }
+        }
+    });
+    Ok(out)
+}
@@ -0,0 +1,164 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{Data, DeriveInput, Error, Result};
+use super::{binding, camel_case, impl_via, members, nested_visibility, phantom_struct};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let fields = match input.data {
+        Data::Struct(ref data) => &data.fields,
+        _ => return Err(Error::new_spanned(&input.ident, "`Lenses` can only be derived for structs")),
+    };
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let source = quote!(#name #ty_generics);
+    let members = members(fields);
+    let bindings: Vec<_> = (0..members.len()).map(binding).collect();
+
+    let mut out = TokenStream::new();
+    let mut ctors = Vec::new();
+    for (i, field) in fields.iter().enumerate() {
+        let (suffix, ctor) = match field.ident {
+            Some(ref id) => {
+                let id = id.unraw();
+                (camel_case(&id.to_string()), format_ident!("lens_{}", id))
+            }
+            None => (i.to_string(), format_ident!("lens_{}", i)),
+        };
+        let lens = format_ident!("{}{}Lens", name, suffix);
+        let vis = nested_visibility(&input.vis, &field.vis);
+        let member = &members[i];
+        let target = &field.ty;
+        let focus = &bindings[i];
+        let rebuilt = bindings.iter()
+                              .enumerate()
+                              .map(|(j, b)| if i == j { format_ident!("__x") } else { b.clone() });
+        let others: Vec<_> = bindings.iter()
+                                     .enumerate()
+                                     .filter(|&(j, _)| i != j)
+                                     .map(|(_, b)| b)
+                                     .collect();
+        let other_types: Vec<_> = fields.iter()
+                                        .enumerate()
+                                        .filter(|&(j, _)| i != j)
+                                        .map(|(_, field)| &field.ty)
+                                        .collect();
+        let doc = format!("A lens addressing the `{}` field of `{}`.",
+                          quote!(#member),
+                          name);
+
+        out.extend(phantom_struct(&lens, vis, &input.generics, &source, &source, &doc));
+        out.extend(impl_via("impl_traversal_via_partial_lens", &lens, &input.generics));
+        out.extend(impl_via("impl_getter_via_lens", &lens, &input.generics));
+        out.extend(quote! {
+            impl #impl_generics ::refraction::Lenticuloid for #lens #ty_generics #where_clause {
+                type InitialSource = #source;
+                type InitialTarget = #target;
+                type FinalSource = #source;
+                type FinalTarget = #target;
+                type AtInitial = Self;
+                #[inline]
+                fn at_initial(&self) -> Self::AtInitial {
+                    *self
+                }
+                type AtFinal = Self;
+                #[inline]
+                fn at_final(&self) -> Self::AtFinal {
+                    *self
+                }
+            }
+
+            impl #impl_generics ::refraction::PartialLens for #lens #ty_generics #where_clause {
+                type Context = (#(#other_types,)*);
+                #[inline]
+                fn try_get(&self, v: Self::InitialSource) ->
+                    ::std::result::Result<Self::InitialTarget, Self::FinalSource>
+                {
+                    ::std::result::Result::Ok(v.#member)
+                }
+                #[inline]
+                fn try_get_context(&self, v: Self::InitialSource) ->
+                    ::std::result::Result<(Self::InitialTarget, Self::Context), Self::FinalSource>
+                {
+                    let #name { #(#members: #bindings),* } = v;
+                    ::std::result::Result::Ok((#focus, (#(#others,)*)))
+                }
+                #[inline]
+                fn fill_context(&self, c: Self::Context, __x: Self::FinalTarget) -> Self::FinalSource {
+                    let (#(#others,)*) = c;
+                    #name { #(#members: #rebuilt),* }
+                }
+                #[inline]
+                fn set(&self, mut v: Self::InitialSource, x: Self::FinalTarget) -> Self::FinalSource {
+                    v.#member = x;
+                    v
+                }
+                #[inline]
+                fn exchange(&self, mut v: Self::InitialSource, mut x: Self::FinalTarget) ->
+                    (::std::option::Option<Self::InitialTarget>, Self::FinalSource)
+                {
+                    ::std::mem::swap(&mut v.#member, &mut x);
+                    (::std::option::Option::Some(x), v)
+                }
+                #[inline]
+                fn modify<__F>(&self, mut v: Self::InitialSource, f: __F) -> Self::FinalSource
+                    where __F: FnOnce(Self::InitialTarget) -> Self::FinalTarget
+                {
+                    v.#member = f(v.#member);
+                    v
+                }
+                #[inline]
+                fn modify_with<__F, __X>(&self, mut v: Self::InitialSource, f: __F) ->
+                    (Self::FinalSource, ::std::option::Option<__X>)
+                    where __F: FnOnce(Self::InitialTarget) -> (Self::FinalTarget, __X)
+                {
+                    let (x, aux) = f(v.#member);
+                    v.#member = x;
+                    (v, ::std::option::Option::Some(aux))
+                }
+            }
+
+
+            impl #impl_generics ::refraction::Lens for #lens #ty_generics #where_clause {
+                #[inline]
+                fn get(&self, v: Self::InitialSource) -> Self::InitialTarget {
+                    v.#member
+                }
+            }
+
+            impl #impl_generics ::refraction::LensRef for #lens #ty_generics #where_clause {
+                #[inline]
+                fn view_ref<'__a>(&self, v: &'__a Self::InitialSource) -> &'__a Self::InitialTarget
+                    where Self: '__a
+                {
+                    &v.#member
+                }
+            }
+
+            impl #impl_generics ::refraction::LensMut for #lens #ty_generics #where_clause {
+                #[inline]
+                fn view_mut<'__a>(&self, v: &'__a mut Self::InitialSource) ->
+                    &'__a mut Self::InitialTarget
+                    where Self: '__a
+                {
+                    &mut v.#member
+                }
+            }
+        });
+        ctors.push(quote! {
+            #[inline]
+            #vis fn #ctor() -> #lens #ty_generics {
+                #lens::mk()
+            }
+        });
+    }
+
+    if !ctors.is_empty() {
+        out.extend(quote! {
+            impl #impl_generics #name #ty_generics #where_clause {
+                #(#ctors)*
+            }
+        });
+    }
+    Ok(out)
+}
@@ -0,0 +1,213 @@
+//! Custom derives for the [`refraction`](https://docs.rs/refraction) lens
+//! library.
+//!
+//! Each derive generates zero-sized lenticuloid types alongside the item it
+//! is attached to, together with associated functions on that item for
+//! constructing them.  The generated code refers to the `refraction` crate by
+//! name, so it must be available as a dependency.
+
+extern crate proc_macro;
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{DeriveInput, Fields, GenericParam, Generics, Ident, Index, Member, Visibility,
+          parse_macro_input};
+
+mod iso;
+mod lenses;
+mod prisms;
+
+/// Derive a `Lens` for each field of a struct.
+///
+/// For a struct `Point` with a field `x`, this generates a type `PointXLens`
+/// and an associated function `Point::lens_x()`; for the fields of a tuple
+/// struct `Pair`, it generates `Pair0Lens`, `Pair1Lens`, and so on, with
+/// `Pair::lens_0()` and friends.  Each lens is only as visible as the field
+/// it addresses.
+#[proc_macro_derive(Lenses)]
+pub fn derive_lenses(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    lenses::expand(&input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// Derive a `Prism` for each variant of an enum.
+///
+/// For an enum `Message` with a variant `Ping`, this generates a type
+/// `MessagePingPrism` and an associated function `Message::prism_ping()`.
+/// The prism targets the variant's payload: `()` for a unit variant, the
+/// field's type for a variant with a single field, and a tuple of the
+/// fields' types (in declaration order) otherwise.
+#[proc_macro_derive(Prisms)]
+pub fn derive_prisms(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    prisms::expand(&input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// Derive an `Iso` between a single-field struct and the value it wraps.
+///
+/// For a newtype `Meters`, this generates a type `MetersIso` and an associated
+/// function `Meters::iso()`, only as visible as the wrapped field.
+///
+/// For a generic newtype such as `Wrapper<T>`, the isomorphism is a
+/// type-changing family: `WrapperIso<A, B>` goes between `Wrapper<A>` and
+/// `Wrapper<B>` for each type parameter that the wrapped field's type
+/// mentions.  The final parameters default to the initial ones, and
+/// `Wrapper::iso()` gives the type-preserving member of the family; use
+/// `WrapperIso::mk()` for the others.
+#[proc_macro_derive(Iso)]
+pub fn derive_iso(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    iso::expand(&input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// Convert a `snake_case` identifier into `CamelCase`.
+fn camel_case(s: &str) -> String {
+    s.split('_')
+     .filter(|w| !w.is_empty())
+     .map(|w| {
+         let mut cs = w.chars();
+         cs.next().map(|c| c.to_uppercase().chain(cs).collect::<String>()).unwrap_or_default()
+     })
+     .collect()
+}
+
+/// Convert a `CamelCase` identifier into `snake_case`, treating a run of
+/// capitals as a single word (so `HTTPRequest` becomes `http_request`).
+fn snake_case(s: &str) -> String {
+    let cs: Vec<char> = s.chars().collect();
+    let mut out = String::new();
+    for (i, &c) in cs.iter().enumerate() {
+        if c.is_uppercase() {
+            let prev_lower = i > 0 && !cs[i - 1].is_uppercase() && cs[i - 1] != '_';
+            let ends_run = i > 0 && cs[i - 1].is_uppercase() &&
+                           cs.get(i + 1).is_some_and(|n| n.is_lowercase());
+            if prev_lower || ends_run {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// How far a visibility reaches, when that can be told from the visibility
+/// alone: private is 0, `pub(crate)` is 1 and `pub` is 2.  Other restricted
+/// visibilities depend on where the item lives, so they have no rank.
+fn visibility_rank(vis: &Visibility) -> Option<u8> {
+    match *vis {
+        Visibility::Public(_) => Some(2),
+        Visibility::Inherited => Some(0),
+        Visibility::Restricted(ref r) if r.path.is_ident("crate") => Some(1),
+        Visibility::Restricted(ref r) if r.path.is_ident("self") => Some(0),
+        Visibility::Restricted(_) => None,
+    }
+}
+
+/// The visibility of something nested inside an item: the narrower of the
+/// item's own visibility and the nested one.  When the two can't be
+/// compared, the item's visibility wins.
+fn nested_visibility<'a>(outer: &'a Visibility, inner: &'a Visibility) -> &'a Visibility {
+    match (visibility_rank(outer), visibility_rank(inner)) {
+        (Some(o), Some(i)) if i < o => inner,
+        (Some(2), None) => inner,
+        _ => outer,
+    }
+}
+
+/// Generate a zero-sized lenticuloid type from `source` to `final_source`,
+/// with the given generics, along with its `mk` constructor and the usual
+/// phantom-friendly trait impls.
+fn phantom_struct(ty: &Ident,
+                  vis: &Visibility,
+                  generics: &Generics,
+                  source: &TokenStream,
+                  final_source: &TokenStream,
+                  doc: &str)
+                  -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let name = ty.to_string();
+    quote! {
+        #[doc = #doc]
+        #vis struct #ty #generics #where_clause {
+            phantom_st: ::std::marker::PhantomData<fn(#source) -> #final_source>,
+        }
+
+        impl #impl_generics #ty #ty_generics #where_clause {
+            #[inline]
+            #vis fn mk() -> Self {
+                #ty { phantom_st: ::std::marker::PhantomData }
+            }
+        }
+
+        impl #impl_generics ::std::fmt::Debug for #ty #ty_generics #where_clause {
+            fn fmt(&self, fm: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                fm.debug_struct(#name)
+                  .field("phantom_st", &self.phantom_st)
+                  .finish()
+            }
+        }
+
+        impl #impl_generics ::std::clone::Clone for #ty #ty_generics #where_clause {
+            #[inline]
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl #impl_generics ::std::marker::Copy for #ty #ty_generics #where_clause {}
+
+        impl #impl_generics ::std::default::Default for #ty #ty_generics #where_clause {
+            #[inline]
+            fn default() -> Self {
+                Self::mk()
+            }
+        }
+    }
+}
+
+/// Generate the impls that `refraction` derives from others with the macro
+/// `mac`, such as `impl_traversal_via_partial_lens`, for `ty` with the given
+/// generics.
+fn impl_via(mac: &str, ty: &Ident, generics: &Generics) -> TokenStream {
+    let mac = Ident::new(mac, Span::call_site());
+    let (_, ty_generics, where_clause) = generics.split_for_impl();
+    // The macro takes bare parameters, which must not carry defaults.
+    let params = generics.params.iter().map(|param| {
+        let mut param = param.clone();
+        match param {
+            GenericParam::Type(ref mut ty) => {
+                ty.eq_token = None;
+                ty.default = None;
+            }
+            GenericParam::Const(ref mut c) => {
+                c.eq_token = None;
+                c.default = None;
+            }
+            GenericParam::Lifetime(_) => {}
+        }
+        param
+    });
+    let predicates = where_clause.map(|w| &w.predicates);
+    quote! {
+        ::refraction::#mac!([#(#params),*] #ty #ty_generics where [#predicates]);
+    }
+}
+
+/// The members by which each of `fields` can be accessed, whether named or
+/// positional.
+fn members(fields: &Fields) -> Vec<Member> {
+    fields.iter()
+          .enumerate()
+          .map(|(i, field)| match field.ident {
+              Some(ref id) => Member::Named(id.clone()),
+              None => Member::Unnamed(Index::from(i)),
+          })
+          .collect()
+}
+
+/// A fresh identifier for a destructured binding.
+fn binding(i: usize) -> Ident {
+    Ident::new(&format!("__field{}", i), Span::call_site())
+}
@@ -0,0 +1,124 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{Data, DeriveInput, Error, Result};
+use super::{binding, impl_via, members, phantom_struct, snake_case};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let variants = match input.data {
+        Data::Enum(ref data) => &data.variants,
+        _ => return Err(Error::new_spanned(&input.ident, "`Prisms` can only be derived for enums")),
+    };
+    let name = &input.ident;
+    let vis = &input.vis;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let source = quote!(#name #ty_generics);
+
+    let mut out = TokenStream::new();
+    let mut ctors = Vec::new();
+    for variant in variants {
+        let var = &variant.ident;
+        let prism = format_ident!("{}{}Prism", name, var.unraw());
+        let ctor = format_ident!("prism_{}", snake_case(&var.unraw().to_string()));
+        let members = members(&variant.fields);
+        let bindings: Vec<_> = (0..members.len()).map(binding).collect();
+        let types: Vec<_> = variant.fields.iter().map(|field| &field.ty).collect();
+        // The payload, both as a type and as a pattern or expression over
+        // `bindings`.
+        let (target, payload) = if types.len() == 1 {
+            (quote!(#(#types)*), quote!(#(#bindings)*))
+        } else {
+            (quote!((#(#types,)*)), quote!((#(#bindings,)*)))
+        };
+        let pattern = quote!(#name::#var { #(#members: #bindings),* });
+        let doc = format!("A prism addressing the `{}` variant of `{}`.", var, name);
+
+        out.extend(phantom_struct(&prism, vis, &input.generics, &source, &source, &doc));
+        out.extend(impl_via("impl_traversal_via_partial_lens", &prism, &input.generics));
+        out.extend(impl_via("impl_review_via_prism", &prism, &input.generics));
+        out.extend(quote! {
+            impl #impl_generics ::refraction::Lenticuloid for #prism #ty_generics #where_clause {
+                type InitialSource = #source;
+                type InitialTarget = #target;
+                type FinalSource = #source;
+                type FinalTarget = #target;
+                type AtInitial = Self;
+                #[inline]
+                fn at_initial(&self) -> Self::AtInitial {
+                    *self
+                }
+                type AtFinal = Self;
+                #[inline]
+                fn at_final(&self) -> Self::AtFinal {
+                    *self
+                }
+            }
+
+            impl #impl_generics ::refraction::PartialLens for #prism #ty_generics #where_clause {
+                type Context = ();
+                #[inline]
+                #[allow(unreachable_patterns)]
+                fn try_get(&self, v: Self::InitialSource) ->
+                    ::std::result::Result<Self::InitialTarget, Self::FinalSource>
+                {
+                    match v {
+                        #pattern => ::std::result::Result::Ok(#payload),
+                        other => ::std::result::Result::Err(other),
+                    }
+                }
+                #[inline]
+                #[allow(unreachable_patterns)]
+                fn try_get_context(&self, v: Self::InitialSource) ->
+                    ::std::result::Result<(Self::InitialTarget, ()), Self::FinalSource>
+                {
+                    match v {
+                        #pattern => ::std::result::Result::Ok((#payload, ())),
+                        other => ::std::result::Result::Err(other),
+                    }
+                }
+                #[inline]
+                fn fill_context(&self, _c: (), x: Self::FinalTarget) -> Self::FinalSource {
+                    ::refraction::Prism::inject(self, x)
+                }
+                #[inline]
+                #[allow(unreachable_patterns)]
+                fn modify_with<__F, __X>(&self, v: Self::InitialSource, f: __F) ->
+                    (Self::FinalSource, ::std::option::Option<__X>)
+                    where __F: FnOnce(Self::InitialTarget) -> (Self::FinalTarget, __X)
+                {
+                    match v {
+                        #pattern => {
+                            let (x, aux) = f(#payload);
+                            (::refraction::Prism::inject(self, x), ::std::option::Option::Some(aux))
+                        }
+                        other => (other, ::std::option::Option::None),
+                    }
+                }
+            }
+
+
+            impl #impl_generics ::refraction::Prism for #prism #ty_generics #where_clause {
+                #[inline]
+                fn inject(&self, v: Self::FinalTarget) -> Self::FinalSource {
+                    let #payload = v;
+                    #pattern
+                }
+            }
+        });
+        ctors.push(quote! {
+            #[inline]
+            #vis fn #ctor() -> #prism #ty_generics {
+                #prism::mk()
+            }
+        });
+    }
+
+    if !ctors.is_empty() {
+        out.extend(quote! {
+            impl #impl_generics #name #ty_generics #where_clause {
+                #(#ctors)*
+            }
+        });
+    }
+    Ok(out)
+}
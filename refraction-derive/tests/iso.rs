@@ -0,0 +1,52 @@
+use refraction::access::Getter;
+use refraction::{ComposeExt, InvertExt, Lens, PartialLens, Prism};
+use refraction_derive::{Iso, Lenses};
+
+#[derive(Debug, PartialEq, Iso)]
+struct Meters(f64);
+
+#[derive(Debug, PartialEq, Iso)]
+struct Tagged<T> {
+    value: T,
+}
+
+#[derive(Debug, PartialEq, Iso)]
+struct Labelled<'a, T: Clone>(Vec<(&'a str, T)>) where T: PartialEq;
+
+#[derive(Debug, PartialEq, Lenses)]
+struct Route {
+    length: Meters,
+}
+
+#[test]
+fn test_tuple_newtype() {
+    let i = Meters::iso();
+    assert_eq!(i.get(Meters(1.5)), 1.5);
+    assert_eq!(i.inject(2.0), Meters(2.0));
+    assert_eq!(i.invert().get(3.0), Meters(3.0));
+}
+
+#[test]
+fn test_named_newtype() {
+    let i = Tagged::<String>::iso();
+    assert_eq!(i.modify(Tagged { value: "a".to_string() }, |s| s + "b"),
+               Tagged { value: "ab".to_string() });
+    assert_eq!(i.invert().view("c".to_string()), Tagged { value: "c".to_string() });
+}
+
+#[test]
+fn test_composed() {
+    let l = Meters::iso().compose(Route::lens_length());
+    let r = l.modify(Route { length: Meters(1.0) }, |x| x * 1000.0);
+    assert_eq!(r, Route { length: Meters(1000.0) });
+}
+
+#[test]
+fn test_type_changing() {
+    let i = TaggedIso::mk();
+    assert_eq!(i.modify(Tagged { value: 7u8 }, |x| x.to_string()),
+               Tagged { value: "7".to_string() });
+    let j = LabelledIso::mk();
+    assert_eq!(j.set(Labelled(vec![("a", 1u8)]), vec![("b", 'c')]), Labelled(vec![("b", 'c')]));
+    assert_eq!(Labelled::iso().invert().get(vec![("d", 2.5)]), Labelled(vec![("d", 2.5)]));
+}
@@ -0,0 +1,86 @@
+use refraction::{ComposeExt, Lens, LensMut, LensRef, PartialLens};
+use refraction_derive::Lenses;
+
+#[derive(Debug, PartialEq, Lenses)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug, PartialEq, Lenses)]
+struct Labelled<'a, T> {
+    label: &'a str,
+    value: T,
+}
+
+#[derive(Debug, PartialEq, Lenses)]
+struct Pair<A, B>(A, B);
+
+mod shapes {
+    use refraction_derive::Lenses;
+
+    #[derive(Lenses)]
+    pub struct Circle {
+        pub radius: f64,
+        #[allow(dead_code)]
+        tag: u8,
+    }
+
+    pub fn circle(radius: f64) -> Circle {
+        Circle { radius, tag: 0 }
+    }
+
+    // A crate-visible field of a private struct only gets a private lens.
+    #[derive(Lenses)]
+    struct Square {
+        pub(crate) side: f64,
+    }
+
+    pub fn square_side(side: f64) -> f64 {
+        use refraction::Lens;
+        let l: SquareSideLens = Square::lens_side();
+        l.get(Square { side })
+    }
+}
+
+#[test]
+fn test_named_fields() {
+    let p = Point { x: 1, y: 2 };
+    assert_eq!(Point::lens_x().get(Point { x: 1, y: 2 }), 1);
+    assert_eq!(Point::lens_y().set(p, 5), Point { x: 1, y: 5 });
+}
+
+#[test]
+fn test_generic_fields() {
+    let l = Labelled::lens_value().compose(Labelled::lens_value());
+    let v = Labelled { label: "outer", value: Labelled { label: "inner", value: 3u8 } };
+    let w = l.modify(v, |x| x + 1);
+    assert_eq!(w.value.value, 4);
+    assert_eq!(Labelled::<u8>::lens_label().get(w.value), "inner");
+}
+
+#[test]
+fn test_in_place() {
+    let l = Labelled::lens_value().compose(Labelled::lens_value());
+    let mut v = Labelled { label: "outer", value: Labelled { label: "inner", value: 3u8 } };
+    *l.view_mut(&mut v) *= 2;
+    assert_eq!(*l.view_ref(&v), 6);
+}
+
+#[test]
+fn test_tuple_fields() {
+    let l = Pair::<u8, String>::lens_1();
+    let (x, mut inj) = l.try_get_inject(Pair(1, "a".into())).unwrap();
+    assert_eq!(x, "a");
+    assert_eq!(inj("b".into()), Some(Pair(1, "b".into())));
+    assert_eq!(inj("c".into()), None);
+    let (old, p) = Pair::<u8, u8>::lens_0().exchange(Pair(1, 2), 3);
+    assert_eq!((old, p), (Some(1), Pair(3, 2)));
+}
+
+#[test]
+fn test_visibility() {
+    let l: shapes::CircleRadiusLens = shapes::Circle::lens_radius();
+    assert_eq!(l.get(shapes::circle(2.5)), 2.5);
+    assert_eq!(shapes::square_side(1.5), 1.5);
+}
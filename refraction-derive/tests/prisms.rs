@@ -0,0 +1,64 @@
+use refraction::access::Review;
+use refraction::{ComposeExt, PartialLens, Prism};
+use refraction_derive::{Lenses, Prisms};
+
+#[derive(Debug, PartialEq, Prisms)]
+enum Message<T> {
+    Ping,
+    Data(T),
+    Move { x: i32, y: i32 },
+}
+
+#[derive(Debug, PartialEq, Lenses)]
+struct Envelope {
+    id: u32,
+    body: Message<String>,
+}
+
+#[test]
+fn test_unit_variant() {
+    let p = Message::<u8>::prism_ping();
+    assert_eq!(p.try_get(Message::Ping), Ok(()));
+    assert_eq!(p.try_get(Message::Data(1)), Err(Message::Data(1)));
+    assert_eq!(p.inject(()), Message::Ping);
+}
+
+#[test]
+fn test_single_field_variant() {
+    let p = Message::prism_data();
+    assert_eq!(p.modify(Message::Data(2u8), |x| x * 2), Message::Data(4));
+    assert_eq!(p.modify(Message::Ping, |x: u8| x * 2), Message::Ping);
+    assert_eq!(p.review(7u8), Message::Data(7));
+}
+
+#[test]
+fn test_multi_field_variant() {
+    let p = Message::<()>::prism_move();
+    let (xy, mut inj) = p.try_get_inject(Message::Move { x: 1, y: 2 }).unwrap();
+    assert_eq!(xy, (1, 2));
+    assert_eq!(inj((3, 4)), Some(Message::Move { x: 3, y: 4 }));
+    assert_eq!(inj((5, 6)), None);
+}
+
+#[test]
+fn test_through_lens() {
+    let l = Message::prism_data().compose(Envelope::lens_body());
+    let v = Envelope { id: 1, body: Message::Data("hi".to_string()) };
+    let w = l.modify(v, |s| s + "!");
+    assert_eq!(w.body, Message::Data("hi!".to_string()));
+    assert_eq!(l.try_get(Envelope { id: 2, body: Message::Ping }).map_err(|e| e.id), Err(2));
+}
+
+#[derive(Debug, PartialEq, Prisms)]
+enum Call {
+    HTTPRequest(String),
+    GetURL,
+    ParseJSONBody(u8),
+}
+
+#[test]
+fn test_acronym_names() {
+    assert_eq!(Call::prism_http_request().try_get(Call::GetURL), Err(Call::GetURL));
+    assert_eq!(Call::prism_get_url().try_get(Call::GetURL), Ok(()));
+    assert_eq!(Call::prism_parse_json_body().inject(3), Call::ParseJSONBody(3));
+}
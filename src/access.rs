@@ -1 +1,504 @@
+//! These lenticuloids restrict what can be done through them to a single
+//! kind of access, such as only reading, only writing, or only building.
 
+use std::fmt::{self, Debug, Formatter};
+use std::marker::PhantomData;
+use super::{Compose, Fold, Identity, Invert, Iso, Lens, Lenticuloid, PartialLens, Prism, Traversal};
+
+/// The supertype of all getter families.
+pub trait Getter: Fold
+    where Self::AtInitial: Getter,
+          Self::AtFinal: Getter
+{
+    fn view(&self, v: Self::InitialSource) -> Self::InitialTarget;
+
+    /// Turn this getter around into a `Review`.  Something that is a `Review`
+    /// as well, like an isomorphism, can be turned around with
+    /// `InvertExt::invert` instead.
+    fn re(self) -> Invert<Self>
+        where Self: Sized
+    {
+        Invert::of(self)
+    }
+}
+
+impl<S, T> Getter for Identity<S, T> {
+    #[inline]
+    fn view(&self, v: S) -> S {
+        v
+    }
+}
+
+impl<LF: Getter, LS: ?Sized> Getter for Compose<LF, LS>
+    where LS: Getter<InitialTarget = LF::InitialSource, FinalTarget = LF::FinalSource>,
+          LF::AtInitial: Getter,
+          LF::AtFinal: Getter,
+          LS::AtInitial: Getter,
+          LS::AtFinal: Getter
+{
+    fn view(&self, v: Self::InitialSource) -> Self::InitialTarget {
+        self.first.view(self.second.view(v))
+    }
+}
+
+impl<L: Review> Getter for Invert<L>
+    where L::AtInitial: Review,
+          L::AtFinal: Review
+{
+    #[inline]
+    fn view(&self, v: Self::InitialSource) -> Self::InitialTarget {
+        self.deinvert.review(v)
+    }
+}
+
+/// A `Getter` that views through an arbitrary function.
+pub struct To<S, A, F> {
+    func: F,
+    phantom_sa: PhantomData<fn(S) -> A>,
+}
+
+impl<S, A, F: Fn(S) -> A> To<S, A, F> {
+    #[inline]
+    pub fn of(f: F) -> Self {
+        To { func: f,
+             phantom_sa: PhantomData, }
+    }
+}
+
+/// Lift a function into a `Getter` (function form).
+#[inline]
+pub fn to<S, A, F: Fn(S) -> A>(f: F) -> To<S, A, F> {
+    To::of(f)
+}
+
+impl<S, A, F> Debug for To<S, A, F> {
+    fn fmt(&self, fm: &mut Formatter) -> fmt::Result {
+        fm.debug_struct("To")
+          .field("phantom_sa", &self.phantom_sa)
+          .finish()
+    }
+}
+
+impl<S, A, F: Clone> Clone for To<S, A, F> {
+    #[inline]
+    fn clone(&self) -> Self {
+        To { func: self.func.clone(),
+             phantom_sa: PhantomData, }
+    }
+}
+
+impl<S, A, F: Copy> Copy for To<S, A, F> {}
+
+impl<S, A, F: Clone + Fn(S) -> A> Lenticuloid for To<S, A, F> {
+    type InitialSource = S;
+
+    type InitialTarget = A;
+
+    type FinalSource = S;
+
+    type FinalTarget = A;
+
+    type AtInitial = Self;
+
+    fn at_initial(&self) -> Self::AtInitial {
+        self.clone()
+    }
+
+    type AtFinal = Self;
+
+    fn at_final(&self) -> Self::AtFinal {
+        self.clone()
+    }
+}
+
+impl<S, A, F: Clone + Fn(S) -> A> Fold for To<S, A, F> {
+    #[inline]
+    fn for_each<G: FnMut(A)>(&self, v: S, mut f: G) {
+        f((self.func)(v))
+    }
+}
+
+impl<S, A, F: Clone + Fn(S) -> A> Getter for To<S, A, F> {
+    #[inline]
+    fn view(&self, v: S) -> A {
+        (self.func)(v)
+    }
+}
+
+/// A `Getter` that views through a lens, hiding its ability to set.
+#[derive(Clone,Copy,Debug,Default)]
+pub struct AsGetter<L> {
+    lens: L,
+}
+
+impl<L: Lens> AsGetter<L>
+    where L::AtInitial: Lens,
+          L::AtFinal: Lens
+{
+    #[inline]
+    pub fn of(l: L) -> Self {
+        AsGetter { lens: l }
+    }
+}
+
+impl<L: Lens> Lenticuloid for AsGetter<L>
+    where L::AtInitial: Lens,
+          L::AtFinal: Lens
+{
+    type InitialSource = L::InitialSource;
+
+    type InitialTarget = L::InitialTarget;
+
+    type FinalSource = L::InitialSource;
+
+    type FinalTarget = L::InitialTarget;
+
+    type AtInitial = AsGetter<L::AtInitial>;
+
+    fn at_initial(&self) -> Self::AtInitial {
+        AsGetter::of(self.lens.at_initial())
+    }
+
+    type AtFinal = AsGetter<L::AtInitial>;
+
+    fn at_final(&self) -> Self::AtFinal {
+        AsGetter::of(self.lens.at_initial())
+    }
+}
+
+impl<L: Lens> Fold for AsGetter<L>
+    where L::AtInitial: Lens,
+          L::AtFinal: Lens
+{
+    #[inline]
+    fn for_each<F>(&self, v: Self::InitialSource, mut f: F)
+        where F: FnMut(Self::InitialTarget)
+    {
+        f(self.lens.get(v))
+    }
+}
+
+impl<L: Lens> Getter for AsGetter<L>
+    where L::AtInitial: Lens,
+          L::AtFinal: Lens
+{
+    #[inline]
+    fn view(&self, v: Self::InitialSource) -> Self::InitialTarget {
+        self.lens.get(v)
+    }
+}
+
+/// The supertype of all setter families.
+pub trait Setter: Lenticuloid
+    where Self::AtInitial: Setter,
+          Self::AtFinal: Setter
+{
+    fn over<F>(&self, v: Self::InitialSource, f: F) -> Self::FinalSource
+        where F: FnMut(Self::InitialTarget) -> Self::FinalTarget;
+
+    fn set_all(&self, v: Self::InitialSource, x: Self::FinalTarget) -> Self::FinalSource
+        where Self::FinalTarget: Clone
+    {
+        self.over(v, |_| x.clone())
+    }
+}
+
+impl<S, T> Setter for Identity<S, T> {
+    #[inline]
+    fn over<F: FnMut(S) -> T>(&self, v: S, mut f: F) -> T {
+        f(v)
+    }
+
+    #[inline]
+    fn set_all(&self, _v: S, x: T) -> T {
+        x
+    }
+}
+
+impl<LF: Setter, LS: ?Sized> Setter for Compose<LF, LS>
+    where LS: Setter<InitialTarget = LF::InitialSource, FinalTarget = LF::FinalSource>,
+          LF::AtInitial: Setter,
+          LF::AtFinal: Setter,
+          LS::AtInitial: Setter,
+          LS::AtFinal: Setter
+{
+    fn over<F>(&self, v: Self::InitialSource, mut f: F) -> Self::FinalSource
+        where F: FnMut(Self::InitialTarget) -> Self::FinalTarget
+    {
+        self.second.over(v, |q| self.first.over(q, &mut f))
+    }
+}
+
+impl<L: Iso> Setter for Invert<L>
+    where L::AtInitial: Iso,
+          L::AtFinal: Iso
+{
+    #[inline]
+    fn over<F>(&self, v: Self::InitialSource, f: F) -> Self::FinalSource
+        where F: FnMut(Self::InitialTarget) -> Self::FinalTarget
+    {
+        self.modify(v, f)
+    }
+}
+
+/// A `Setter` that maps over its targets with an arbitrary higher-order
+/// function.
+pub struct Sets<S, A, F> {
+    func: F,
+    phantom_sa: PhantomData<fn(S) -> A>,
+}
+
+impl<S, A, F> Sets<S, A, F>
+    where F: Fn(&mut dyn FnMut(A) -> A, S) -> S
+{
+    #[inline]
+    pub fn of(f: F) -> Self {
+        Sets { func: f,
+               phantom_sa: PhantomData, }
+    }
+}
+
+/// Lift a higher-order mapping function into a `Setter` (function form).
+#[inline]
+pub fn sets<S, A, F>(f: F) -> Sets<S, A, F>
+    where F: Fn(&mut dyn FnMut(A) -> A, S) -> S
+{
+    Sets::of(f)
+}
+
+impl<S, A, F> Debug for Sets<S, A, F> {
+    fn fmt(&self, fm: &mut Formatter) -> fmt::Result {
+        fm.debug_struct("Sets")
+          .field("phantom_sa", &self.phantom_sa)
+          .finish()
+    }
+}
+
+impl<S, A, F: Clone> Clone for Sets<S, A, F> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Sets { func: self.func.clone(),
+               phantom_sa: PhantomData, }
+    }
+}
+
+impl<S, A, F: Copy> Copy for Sets<S, A, F> {}
+
+impl<S, A, F> Lenticuloid for Sets<S, A, F>
+    where F: Clone + Fn(&mut dyn FnMut(A) -> A, S) -> S
+{
+    type InitialSource = S;
+
+    type InitialTarget = A;
+
+    type FinalSource = S;
+
+    type FinalTarget = A;
+
+    type AtInitial = Self;
+
+    fn at_initial(&self) -> Self::AtInitial {
+        self.clone()
+    }
+
+    type AtFinal = Self;
+
+    fn at_final(&self) -> Self::AtFinal {
+        self.clone()
+    }
+}
+
+impl<S, A, F> Setter for Sets<S, A, F>
+    where F: Clone + Fn(&mut dyn FnMut(A) -> A, S) -> S
+{
+    #[inline]
+    fn over<G: FnMut(A) -> A>(&self, v: S, mut f: G) -> S {
+        (self.func)(&mut f, v)
+    }
+}
+
+/// A `Setter` that modifies through a traversal, hiding its ability to read.
+#[derive(Clone,Copy,Debug,Default)]
+pub struct AsSetter<L> {
+    traversal: L,
+}
+
+impl<L: Traversal> AsSetter<L>
+    where L::AtInitial: Traversal,
+          L::AtFinal: Traversal
+{
+    #[inline]
+    pub fn of(l: L) -> Self {
+        AsSetter { traversal: l }
+    }
+}
+
+impl<L: Traversal> Lenticuloid for AsSetter<L>
+    where L::AtInitial: Traversal,
+          L::AtFinal: Traversal
+{
+    type InitialSource = L::InitialSource;
+
+    type InitialTarget = L::InitialTarget;
+
+    type FinalSource = L::FinalSource;
+
+    type FinalTarget = L::FinalTarget;
+
+    type AtInitial = AsSetter<L::AtInitial>;
+
+    fn at_initial(&self) -> Self::AtInitial {
+        AsSetter::of(self.traversal.at_initial())
+    }
+
+    type AtFinal = AsSetter<L::AtFinal>;
+
+    fn at_final(&self) -> Self::AtFinal {
+        AsSetter::of(self.traversal.at_final())
+    }
+}
+
+impl<L: Traversal> Setter for AsSetter<L>
+    where L::AtInitial: Traversal,
+          L::AtFinal: Traversal
+{
+    #[inline]
+    fn over<F>(&self, v: Self::InitialSource, f: F) -> Self::FinalSource
+        where F: FnMut(Self::InitialTarget) -> Self::FinalTarget
+    {
+        self.traversal.modify_all(v, f)
+    }
+}
+
+/// The supertype of all review families.
+pub trait Review: Lenticuloid
+    where Self::AtInitial: Review,
+          Self::AtFinal: Review
+{
+    fn review(&self, v: Self::FinalTarget) -> Self::FinalSource;
+
+    /// Turn this review around into a `Getter`.  Something that is a `Getter`
+    /// as well, like an isomorphism, can be turned around with
+    /// `InvertExt::invert` instead.
+    fn re(self) -> Invert<Self>
+        where Self: Sized
+    {
+        Invert::of(self)
+    }
+}
+
+impl<S, T> Review for Identity<S, T> {
+    #[inline]
+    fn review(&self, v: T) -> T {
+        v
+    }
+}
+
+impl<LF: Review, LS: ?Sized> Review for Compose<LF, LS>
+    where LS: Review<InitialTarget = LF::InitialSource, FinalTarget = LF::FinalSource>,
+          LF::AtInitial: Review,
+          LF::AtFinal: Review,
+          LS::AtInitial: Review,
+          LS::AtFinal: Review
+{
+    fn review(&self, v: Self::FinalTarget) -> Self::FinalSource {
+        self.second.review(self.first.review(v))
+    }
+}
+
+impl<L: Getter> Review for Invert<L>
+    where L::AtInitial: Getter,
+          L::AtFinal: Getter
+{
+    #[inline]
+    fn review(&self, v: Self::FinalTarget) -> Self::FinalSource {
+        self.deinvert.view(v)
+    }
+}
+
+/// A `Review` that builds through a prism, hiding its ability to match.
+#[derive(Clone,Copy,Debug,Default)]
+pub struct AsReview<L> {
+    prism: L,
+}
+
+impl<L: Prism> AsReview<L>
+    where L::AtInitial: Prism,
+          L::AtFinal: Prism
+{
+    #[inline]
+    pub fn of(l: L) -> Self {
+        AsReview { prism: l }
+    }
+}
+
+impl<L: Prism> Lenticuloid for AsReview<L>
+    where L::AtInitial: Prism,
+          L::AtFinal: Prism
+{
+    type InitialSource = L::FinalSource;
+
+    type InitialTarget = L::FinalTarget;
+
+    type FinalSource = L::FinalSource;
+
+    type FinalTarget = L::FinalTarget;
+
+    type AtInitial = AsReview<L::AtFinal>;
+
+    fn at_initial(&self) -> Self::AtInitial {
+        AsReview::of(self.prism.at_final())
+    }
+
+    type AtFinal = AsReview<L::AtFinal>;
+
+    fn at_final(&self) -> Self::AtFinal {
+        AsReview::of(self.prism.at_final())
+    }
+}
+
+impl<L: Prism> Review for AsReview<L>
+    where L::AtInitial: Prism,
+          L::AtFinal: Prism
+{
+    #[inline]
+    fn review(&self, v: Self::FinalTarget) -> Self::FinalSource {
+        self.prism.inject(v)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::{ComposeExt, Lens, Traversal};
+
+    #[test]
+    fn test_getter_compose() {
+        struct Order {
+            amounts: Vec<u32>,
+        }
+        let total = to(|v: Vec<u32>| v.iter().sum::<u32>());
+        let l = total.compose(field_lens!(Order => amounts: Vec<u32>).as_getter());
+        assert_eq!(l.view(Order { amounts: vec![2, 3] }), 5);
+        let l = to(|x: u32| x * 2).compose(::Identity::<u32, u32>::mk());
+        assert_eq!(l.to_vec(4), vec![8]);
+    }
+
+    #[test]
+    fn test_setter() {
+        let l = sets(|f: &mut dyn FnMut(u32) -> u32, v: Vec<u32>| v.into_iter().map(f).collect());
+        assert_eq!(l.over(vec![1, 2], |x| x + 1), vec![2, 3]);
+        let l = l.compose(::collections::Each::<Vec<Vec<u32>>>::mk().as_setter());
+        assert_eq!(l.set_all(vec![vec![1], vec![2, 3]], 0), vec![vec![0], vec![0, 0]]);
+    }
+
+    #[test]
+    fn test_review() {
+        let r = ::errors::Some_::<u32>::mk().as_review();
+        assert_eq!(r.review(3), Some(3));
+        assert_eq!(r.re().view(4), Some(4));
+        let r = to(|x: u32| x.to_string()).re();
+        assert_eq!(r.review(5), "5");
+        assert_eq!(r.re().view(6), "6");
+    }
+}
@@ -0,0 +1,357 @@
+//! `refract`, a small `jq`-like tool for reading and editing JSON documents
+//! through the same lens paths (`servers[2].ports.http`) that
+//! `refraction::dynamic::Path` parses.
+
+extern crate refraction;
+extern crate serde_json;
+
+use std::env;
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::process;
+use refraction::dynamic::{Path, Segment};
+use refraction::json::{AsArray, AsBool, AsNumber, AsObject, AsStr};
+use refraction::{ComposeExt, PartialLens};
+use serde_json::{Number, Value};
+
+const USAGE: &str = "\
+usage: refract <command> <path> [<argument>] [<file>]
+
+Reads JSON from <file>, or from standard input if it is absent or `-`.
+
+commands:
+    get <path>            print the value at <path>
+    set <path> <json>     replace the value at <path>, or add a new key
+    modify <path> <expr>  update the value at <path> with <expr>
+    delete <path>         remove the value at <path> from its parent
+    list <path>           print the paths of the children of <path>
+
+expressions:
+    + N, - N, * N, / N    arithmetic on numbers
+    ++ STRING             append to a string
+    not                   negate a boolean
+    upper, lower          change the case of a string";
+
+/// An update to apply to a value in place, parsed from the command line.
+#[derive(Clone,Debug,PartialEq)]
+enum Expr {
+    Arith(char, f64),
+    Append(String),
+    Not,
+    Upper,
+    Lower,
+}
+
+impl Expr {
+    fn parse(s: &str) -> Result<Self, String> {
+        let s = s.trim();
+        match s {
+            "not" => return Ok(Expr::Not),
+            "upper" => return Ok(Expr::Upper),
+            "lower" => return Ok(Expr::Lower),
+            _ => {}
+        }
+        if let Some(suffix) = s.strip_prefix("++") {
+            return Ok(Expr::Append(suffix.trim_start().to_owned()));
+        }
+        let mut chars = s.chars();
+        match chars.next() {
+            Some(op @ '+') | Some(op @ '-') | Some(op @ '*') | Some(op @ '/') => {
+                let operand = chars.as_str().trim();
+                operand.parse()
+                       .map(|n| Expr::Arith(op, n))
+                       .map_err(|_| {
+                           format!("expected a number after `{}`, found `{}`", op, operand)
+                       })
+            }
+            _ => Err(format!("unknown expression `{}`", s)),
+        }
+    }
+
+    /// Apply this update to the value at `p`, which must be of the kind the
+    /// update expects.  On failure `v` comes back unchanged, with the reason
+    /// if it was something other than a missing or mismatched value.
+    fn apply(&self, p: &Path, v: Value) -> Result<Value, (Value, Option<&'static str>)> {
+        let l = || p.lens();
+        let modified = match *self {
+            Expr::Arith(op, n) => {
+                AsNumber.compose(l()).modify_with(v, |x| match arith(op, &x, n) {
+                    Ok(y) => (y, Ok(())),
+                    Err(e) => (x, Err(e)),
+                })
+            }
+            Expr::Append(ref s) => AsStr.compose(l()).modify_with(v, |x| (x + s, Ok(()))),
+            Expr::Not => AsBool.compose(l()).modify_with(v, |b| (!b, Ok(()))),
+            Expr::Upper => AsStr.compose(l()).modify_with(v, |x| (x.to_uppercase(), Ok(()))),
+            Expr::Lower => AsStr.compose(l()).modify_with(v, |x| (x.to_lowercase(), Ok(()))),
+        };
+        match modified {
+            (v, Some(Ok(()))) => Ok(v),
+            (v, Some(Err(e))) => Err((v, Some(e))),
+            (v, None) => Err((v, None)),
+        }
+    }
+}
+
+/// Do arithmetic on a number, staying within integers where possible so that
+/// `+ 1` on `80` gives `81` rather than `81.0`.  Integer overflow is an error
+/// rather than a silent loss of precision, and so is a result that JSON
+/// cannot represent.
+fn arith(op: char, x: &Number, n: f64) -> Result<Number, &'static str> {
+    if op == '/' && n == 0.0 {
+        return Err("division by zero");
+    }
+    // `i64::MAX as f64` rounds up to 2^63, which is out of range.
+    let integral = n.fract() == 0.0 && n >= i64::MIN as f64 && n < i64::MAX as f64;
+    match x.as_i64() {
+        Some(x) if integral && op != '/' => {
+            let n = n as i64;
+            match op {
+                '+' => x.checked_add(n),
+                '-' => x.checked_sub(n),
+                _ => x.checked_mul(n),
+            }
+            .map(Number::from)
+            .ok_or("the result overflows a 64-bit integer")
+        }
+        _ => {
+            x.as_f64()
+             .and_then(|x| Number::from_f64(arith_f64(op, x, n)))
+             .ok_or("the result is not a finite number")
+        }
+    }
+}
+
+fn arith_f64(op: char, x: f64, n: f64) -> f64 {
+    match op {
+        '+' => x + n,
+        '-' => x - n,
+        '*' => x * n,
+        _ => x / n,
+    }
+}
+
+/// Explain why `p` does not address anything in `v`, naming the first
+/// missing segment.
+fn missing(p: &Path, v: &Value) -> String {
+    match p.get(v) {
+        Ok(_) => format!("nothing at `{}`", p),
+        Err(e) => e.to_string(),
+    }
+}
+
+fn parent(p: &Path) -> Result<(Path, &Segment), String> {
+    match p.segments().split_last() {
+        Some((last, init)) => Ok((Path::of(init.to_vec()), last)),
+        None => Err("the whole document has no parent".to_owned()),
+    }
+}
+
+fn child(p: &Path, seg: Segment) -> Path {
+    let mut segments = p.segments().to_vec();
+    segments.push(seg);
+    Path::of(segments)
+}
+
+fn pretty(v: &Value) -> String {
+    serde_json::to_string_pretty(v).expect("JSON values always serialize")
+}
+
+/// Run a command on an already-parsed document, returning what to print.
+fn run(command: &str, p: &Path, arg: Option<&str>, v: Value) -> Result<String, String> {
+    let l = p.lens();
+    match (command, arg) {
+        ("get", None) => l.try_get(v).map(|x| pretty(&x)).map_err(|v| missing(p, &v)),
+        ("set", Some(arg)) => {
+            let x: Value = serde_json::from_str(arg)
+                .map_err(|e| format!("invalid JSON argument: {}", e))?;
+            match l.try_get_context(v) {
+                Ok((_, c)) => Ok(pretty(&l.fill_context(c, x))),
+                Err(v) => {
+                    // A missing key is added to its parent object, if there is
+                    // one.
+                    let (init, last) = parent(p)?;
+                    match *last {
+                        Segment::Key(ref k) => {
+                            let pl = init.lens();
+                            match AsObject.compose(pl).modify_with(v, |mut m| {
+                                m.insert(k.clone(), x);
+                                (m, ())
+                            }) {
+                                (v, Some(())) => Ok(pretty(&v)),
+                                (v, None) => Err(missing(p, &v)),
+                            }
+                        }
+                        Segment::Index(_) => Err(missing(p, &v)),
+                    }
+                }
+            }
+        }
+        ("modify", Some(arg)) => {
+            let e = Expr::parse(arg)?;
+            match e.apply(p, v) {
+                Ok(v) => Ok(pretty(&v)),
+                Err((v, why)) => {
+                    match (l.try_get(v), why) {
+                        (Ok(x), Some(why)) => {
+                            Err(format!("cannot apply `{}` to {}: {}", arg, x, why))
+                        }
+                        (Ok(x), None) => Err(format!("cannot apply `{}` to {}", arg, x)),
+                        (Err(v), _) => Err(missing(p, &v)),
+                    }
+                }
+            }
+        }
+        ("delete", None) => {
+            let (init, last) = parent(p)?;
+            let pl = init.lens();
+            let removed = match *last {
+                Segment::Key(ref k) => {
+                    AsObject.compose(pl).modify_with(v, |mut m| {
+                        let found = m.remove(k).is_some();
+                        (m, found)
+                    })
+                }
+                Segment::Index(i) => {
+                    AsArray.compose(pl).modify_with(v, |mut a| {
+                        let found = i < a.len();
+                        if found {
+                            a.remove(i);
+                        }
+                        (a, found)
+                    })
+                }
+            };
+            match removed {
+                (v, Some(true)) => Ok(pretty(&v)),
+                (v, _) => Err(missing(p, &v)),
+            }
+        }
+        ("list", None) => {
+            let x = l.try_get(v).map_err(|v| missing(p, &v))?;
+            let children: Vec<Path> = match AsObject.try_get(x) {
+                Ok(m) => m.into_iter().map(|(k, _)| child(p, Segment::Key(k))).collect(),
+                Err(x) => {
+                    match AsArray.try_get(x) {
+                        Ok(a) => (0..a.len()).map(|i| child(p, Segment::Index(i))).collect(),
+                        Err(x) => return Err(format!("{} has no children", x)),
+                    }
+                }
+            };
+            Ok(children.iter().map(|c| c.to_string()).collect::<Vec<_>>().join("\n"))
+        }
+        ("get", Some(_)) | ("delete", Some(_)) | ("list", Some(_)) => {
+            Err(format!("`{}` takes no argument", command))
+        }
+        ("set", None) | ("modify", None) => Err(format!("`{}` needs an argument", command)),
+        _ => Err(format!("unknown command `{}`", command)),
+    }
+}
+
+fn read_input(file: Option<&str>) -> Result<Value, String> {
+    let mut text = String::new();
+    match file {
+        None | Some("-") => io::stdin().read_to_string(&mut text).map(|_| ()),
+        Some(name) => File::open(name).and_then(|mut f| f.read_to_string(&mut text)).map(|_| ()),
+    }
+    .map_err(|e| format!("could not read input: {}", e))?;
+    serde_json::from_str(&text).map_err(|e| format!("invalid JSON input: {}", e))
+}
+
+fn main_result() -> Result<String, String> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.iter().any(|a| a == "-h" || a == "--help") {
+        return Ok(USAGE.to_owned());
+    }
+    let (command, path, rest) = match args.split_first() {
+        Some((command, rest)) if !rest.is_empty() => (command.as_str(), &rest[0], &rest[1..]),
+        _ => return Err(USAGE.to_owned()),
+    };
+    let p = Path::parse(path).map_err(|e| format!("invalid path `{}`: {}", path, e))?;
+    let takes_arg = command == "set" || command == "modify";
+    let (arg, file) = match (takes_arg, rest) {
+        (true, [arg]) => (Some(arg.as_str()), None),
+        (true, [arg, file]) => (Some(arg.as_str()), Some(file.as_str())),
+        (false, []) => (None, None),
+        (false, [file]) => (None, Some(file.as_str())),
+        _ => return Err(USAGE.to_owned()),
+    };
+    run(command, &p, arg, read_input(file)?)
+}
+
+fn main() {
+    match main_result() {
+        Ok(out) => println!("{}", out),
+        Err(e) => {
+            let _ = writeln!(io::stderr(), "refract: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn doc() -> Value {
+        serde_json::from_str(r#"{"servers": [{"name": "a", "ports": {"http": 80}, "up": true}]}"#)
+            .unwrap()
+    }
+
+    fn run_on(command: &str, path: &str, arg: Option<&str>) -> Result<Value, String> {
+        run(command, &Path::parse(path).unwrap(), arg, doc())
+            .map(|out| serde_json::from_str(&out).unwrap())
+    }
+
+    #[test]
+    fn test_expr_parse() {
+        assert_eq!(Expr::parse("+ 1"), Ok(Expr::Arith('+', 1.0)));
+        assert_eq!(Expr::parse("/2.5"), Ok(Expr::Arith('/', 2.5)));
+        assert_eq!(Expr::parse("++ -dev"), Ok(Expr::Append("-dev".to_owned())));
+        assert_eq!(Expr::parse(" not "), Ok(Expr::Not));
+        assert!(Expr::parse("* x").is_err());
+        assert!(Expr::parse("frobnicate").is_err());
+    }
+
+    #[test]
+    fn test_commands() {
+        assert_eq!(run_on("get", "servers[0].ports.http", None), Ok(Value::from(80)));
+        let v = run_on("modify", "servers[0].ports.http", Some("+ 8000")).unwrap();
+        assert_eq!(v["servers"][0]["ports"]["http"], Value::from(8080));
+        let v = run_on("modify", "servers[0].name", Some("upper")).unwrap();
+        assert_eq!(v["servers"][0]["name"], Value::from("A"));
+        let v = run_on("set", "servers[0].ports.https", Some("443")).unwrap();
+        assert_eq!(v["servers"][0]["ports"]["https"], Value::from(443));
+        let v = run_on("delete", "servers[0].up", None).unwrap();
+        assert_eq!(v["servers"][0].get("up"), None);
+        let listed = run("list", &Path::parse("servers[0]").unwrap(), None, doc());
+        assert_eq!(listed, Ok("servers[0].name\nservers[0].ports\nservers[0].up".to_owned()));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(run_on("get", "servers[1].name", None),
+                   Err("missing path segment `[1]` at depth 1".to_owned()));
+        assert_eq!(run_on("modify", "servers[0].up", Some("+ 1")),
+                   Err("cannot apply `+ 1` to true".to_owned()));
+        assert!(run_on("set", "servers[3]", Some("1")).is_err());
+        let big: Value = serde_json::from_str(r#"{"a": 9223372036854775800, "b": 5}"#).unwrap();
+        let modify = |path: &str, expr: &str| {
+            run("modify", &Path::parse(path).unwrap(), Some(expr), big.clone())
+        };
+        assert_eq!(modify("a", "+ 100"),
+                   Err("cannot apply `+ 100` to 9223372036854775800: the result overflows a \
+                        64-bit integer"
+                           .to_owned()));
+        assert_eq!(modify("a", "- 100").map(|out| serde_json::from_str(&out).unwrap()),
+                   Ok(serde_json::json!({"a": 9223372036854775700i64, "b": 5})));
+        assert_eq!(modify("b", "* 1e30").map(|out| serde_json::from_str::<Value>(&out).unwrap()),
+                   Ok(serde_json::json!({"a": 9223372036854775800i64, "b": 5e30})));
+        assert_eq!(modify("b", "/ 0"),
+                   Err("cannot apply `/ 0` to 5: division by zero".to_owned()));
+        assert_eq!(modify("b", "* 1e308"),
+                   Err("cannot apply `* 1e308` to 5: the result is not a finite number"
+                           .to_owned()));
+        assert!(run_on("delete", "", None).is_err());
+    }
+}
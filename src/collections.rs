@@ -1 +1,1672 @@
+//! These lenticuloids address the elements of the standard library's
+//! collection types.
 
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::fmt::{self, Debug, Formatter};
+use std::hash::{BuildHasher, Hash};
+use std::marker::PhantomData;
+use super::access::Setter;
+use super::{Fold, Lens, Lenticuloid, PartialLens, Prism, Traversal};
+
+/// Collections that can give up the element at a given index, leaving behind
+/// a hole from which the collection can later be rebuilt.
+pub trait Ixed<I: ?Sized>: Sized {
+    type Item;
+
+    type Hole;
+
+    /// Take the element at index `i` out of the collection, or give the
+    /// collection back untouched if there is no such element.
+    fn take_at(self, i: &I) -> Result<(Self::Item, Self::Hole), Self>;
+
+    /// Rebuild the collection by putting an element back into its hole.
+    fn fill_at(hole: Self::Hole, x: Self::Item) -> Self;
+}
+
+impl<T> Ixed<usize> for Vec<T> {
+    type Item = T;
+
+    type Hole = (Vec<T>, usize);
+
+    #[inline]
+    fn take_at(mut self, i: &usize) -> Result<(T, (Vec<T>, usize)), Vec<T>> {
+        if *i < self.len() {
+            Ok((self.swap_remove(*i), (self, *i)))
+        } else {
+            Err(self)
+        }
+    }
+
+    #[inline]
+    fn fill_at((mut v, i): (Vec<T>, usize), x: T) -> Vec<T> {
+        v.push(x);
+        let last = v.len() - 1;
+        v.swap(i, last);
+        v
+    }
+}
+
+impl<T> Ixed<usize> for VecDeque<T> {
+    type Item = T;
+
+    type Hole = (VecDeque<T>, usize);
+
+    #[inline]
+    fn take_at(mut self, i: &usize) -> Result<(T, (VecDeque<T>, usize)), VecDeque<T>> {
+        match self.swap_remove_back(*i) {
+            Some(x) => Ok((x, (self, *i))),
+            None => Err(self),
+        }
+    }
+
+    #[inline]
+    fn fill_at((mut v, i): (VecDeque<T>, usize), x: T) -> VecDeque<T> {
+        v.push_back(x);
+        let last = v.len() - 1;
+        v.swap(i, last);
+        v
+    }
+}
+
+impl<T, const N: usize> Ixed<usize> for [T; N] {
+    type Item = T;
+
+    type Hole = ([Option<T>; N], usize);
+
+    #[inline]
+    fn take_at(self, i: &usize) -> Result<(T, ([Option<T>; N], usize)), [T; N]> {
+        if *i < N {
+            let mut slots = self.map(Some);
+            let x = slots[*i].take().unwrap_or_else(|| unreachable!());
+            Ok((x, (slots, *i)))
+        } else {
+            Err(self)
+        }
+    }
+
+    #[inline]
+    fn fill_at((mut slots, i): ([Option<T>; N], usize), x: T) -> [T; N] {
+        slots[i] = Some(x);
+        slots.map(|x_opt| x_opt.unwrap_or_else(|| unreachable!()))
+    }
+}
+
+impl<K, V, Q: ?Sized, S> Ixed<Q> for HashMap<K, V, S>
+    where K: Borrow<Q> + Hash + Eq,
+          Q: Hash + Eq,
+          S: BuildHasher
+{
+    type Item = V;
+
+    type Hole = (HashMap<K, V, S>, K);
+
+    #[inline]
+    fn take_at(mut self, k: &Q) -> Result<(V, (HashMap<K, V, S>, K)), HashMap<K, V, S>> {
+        match self.remove_entry(k) {
+            Some((k, x)) => Ok((x, (self, k))),
+            None => Err(self),
+        }
+    }
+
+    #[inline]
+    fn fill_at((mut m, k): (HashMap<K, V, S>, K), x: V) -> HashMap<K, V, S> {
+        m.insert(k, x);
+        m
+    }
+}
+
+impl<K, V, Q: ?Sized> Ixed<Q> for BTreeMap<K, V>
+    where K: Borrow<Q> + Ord,
+          Q: Ord
+{
+    type Item = V;
+
+    type Hole = (BTreeMap<K, V>, K);
+
+    #[inline]
+    fn take_at(mut self, k: &Q) -> Result<(V, (BTreeMap<K, V>, K)), BTreeMap<K, V>> {
+        match self.remove_entry(k) {
+            Some((k, x)) => Ok((x, (self, k))),
+            None => Err(self),
+        }
+    }
+
+    #[inline]
+    fn fill_at((mut m, k): (BTreeMap<K, V>, K), x: V) -> BTreeMap<K, V> {
+        m.insert(k, x);
+        m
+    }
+}
+
+/// Maps whose entries can be inserted and removed by key.
+pub trait Keyed<K>: Sized {
+    type Value;
+
+    /// Remove the entry under `k`, if there is one, and return its value.
+    fn remove_key(&mut self, k: &K) -> Option<Self::Value>;
+
+    /// Insert an entry under `k`, replacing any previous one.
+    fn insert_key(&mut self, k: K, x: Self::Value);
+}
+
+impl<K, V, S> Keyed<K> for HashMap<K, V, S>
+    where K: Hash + Eq,
+          S: BuildHasher
+{
+    type Value = V;
+
+    #[inline]
+    fn remove_key(&mut self, k: &K) -> Option<V> {
+        self.remove(k)
+    }
+
+    #[inline]
+    fn insert_key(&mut self, k: K, x: V) {
+        self.insert(k, x);
+    }
+}
+
+impl<K: Ord, V> Keyed<K> for BTreeMap<K, V> {
+    type Value = V;
+
+    #[inline]
+    fn remove_key(&mut self, k: &K) -> Option<V> {
+        self.remove(k)
+    }
+
+    #[inline]
+    fn insert_key(&mut self, k: K, x: V) {
+        self.insert(k, x);
+    }
+}
+
+/// Sets whose members can be inserted and removed.
+pub trait Membered<T>: Sized {
+    /// Remove `x` from the set, returning whether it was present.
+    fn remove_member(&mut self, x: &T) -> bool;
+
+    /// Insert `x` into the set.
+    fn insert_member(&mut self, x: T);
+}
+
+impl<T, S> Membered<T> for HashSet<T, S>
+    where T: Hash + Eq,
+          S: BuildHasher
+{
+    #[inline]
+    fn remove_member(&mut self, x: &T) -> bool {
+        self.remove(x)
+    }
+
+    #[inline]
+    fn insert_member(&mut self, x: T) {
+        self.insert(x);
+    }
+}
+
+impl<T: Ord> Membered<T> for BTreeSet<T> {
+    #[inline]
+    fn remove_member(&mut self, x: &T) -> bool {
+        self.remove(x)
+    }
+
+    #[inline]
+    fn insert_member(&mut self, x: T) {
+        self.insert(x);
+    }
+}
+
+/// Sequences that can be taken apart and rebuilt at either end.
+pub trait Sequence: Sized {
+    type Item;
+
+    /// Split off the first element, or give the sequence back untouched if it
+    /// is empty.
+    fn uncons(self) -> Result<(Self::Item, Self), Self>;
+
+    /// Prepend an element.
+    fn cons(x: Self::Item, rest: Self) -> Self;
+
+    /// Split off the last element, or give the sequence back untouched if it
+    /// is empty.
+    fn unsnoc(self) -> Result<(Self, Self::Item), Self>;
+
+    /// Append an element.
+    fn snoc(init: Self, x: Self::Item) -> Self;
+}
+
+impl<T> Sequence for Vec<T> {
+    type Item = T;
+
+    #[inline]
+    fn uncons(mut self) -> Result<(T, Vec<T>), Vec<T>> {
+        if self.is_empty() {
+            Err(self)
+        } else {
+            Ok((self.remove(0), self))
+        }
+    }
+
+    #[inline]
+    fn cons(x: T, mut rest: Vec<T>) -> Vec<T> {
+        rest.insert(0, x);
+        rest
+    }
+
+    #[inline]
+    fn unsnoc(mut self) -> Result<(Vec<T>, T), Vec<T>> {
+        match self.pop() {
+            Some(x) => Ok((self, x)),
+            None => Err(self),
+        }
+    }
+
+    #[inline]
+    fn snoc(mut init: Vec<T>, x: T) -> Vec<T> {
+        init.push(x);
+        init
+    }
+}
+
+impl<T> Sequence for VecDeque<T> {
+    type Item = T;
+
+    #[inline]
+    fn uncons(mut self) -> Result<(T, VecDeque<T>), VecDeque<T>> {
+        match self.pop_front() {
+            Some(x) => Ok((x, self)),
+            None => Err(self),
+        }
+    }
+
+    #[inline]
+    fn cons(x: T, mut rest: VecDeque<T>) -> VecDeque<T> {
+        rest.push_front(x);
+        rest
+    }
+
+    #[inline]
+    fn unsnoc(mut self) -> Result<(VecDeque<T>, T), VecDeque<T>> {
+        match self.pop_back() {
+            Some(x) => Ok((self, x)),
+            None => Err(self),
+        }
+    }
+
+    #[inline]
+    fn snoc(mut init: VecDeque<T>, x: T) -> VecDeque<T> {
+        init.push_back(x);
+        init
+    }
+}
+
+impl<T> Sequence for Box<[T]> {
+    type Item = T;
+
+    #[inline]
+    fn uncons(self) -> Result<(T, Box<[T]>), Box<[T]>> {
+        self.into_vec()
+            .uncons()
+            .map(|(x, rest)| (x, rest.into_boxed_slice()))
+            .map_err(Vec::into_boxed_slice)
+    }
+
+    #[inline]
+    fn cons(x: T, rest: Box<[T]>) -> Box<[T]> {
+        Vec::cons(x, rest.into_vec()).into_boxed_slice()
+    }
+
+    #[inline]
+    fn unsnoc(self) -> Result<(Box<[T]>, T), Box<[T]>> {
+        self.into_vec()
+            .unsnoc()
+            .map(|(init, x)| (init.into_boxed_slice(), x))
+            .map_err(Vec::into_boxed_slice)
+    }
+
+    #[inline]
+    fn snoc(init: Box<[T]>, x: T) -> Box<[T]> {
+        Vec::snoc(init.into_vec(), x).into_boxed_slice()
+    }
+}
+
+impl Sequence for String {
+    type Item = char;
+
+    #[inline]
+    fn uncons(mut self) -> Result<(char, String), String> {
+        if self.is_empty() {
+            Err(self)
+        } else {
+            Ok((self.remove(0), self))
+        }
+    }
+
+    #[inline]
+    fn cons(x: char, mut rest: String) -> String {
+        rest.insert(0, x);
+        rest
+    }
+
+    #[inline]
+    fn unsnoc(mut self) -> Result<(String, char), String> {
+        match self.pop() {
+            Some(x) => Ok((self, x)),
+            None => Err(self),
+        }
+    }
+
+    #[inline]
+    fn snoc(mut init: String, x: char) -> String {
+        init.push(x);
+        init
+    }
+}
+
+/// Collections whose elements can all be visited in turn.
+pub trait Elements: Sized {
+    type Item;
+
+    /// Consume the collection, passing each element to `f` in turn.
+    fn for_each_element<F: FnMut(Self::Item)>(self, f: F);
+
+    /// Consume the collection, passing each element to `f` in turn until it
+    /// returns `false`; returns whether every element was visited.
+    fn for_each_element_while<F>(self, mut f: F) -> bool
+        where F: FnMut(Self::Item) -> bool
+    {
+        let mut going = true;
+        self.for_each_element(|x| if going {
+            going = f(x);
+        });
+        going
+    }
+}
+
+/// Collections whose elements can all be mapped over, producing a collection
+/// of the same shape.
+pub trait MapElements<T: Elements>: Elements {
+    /// Consume the collection, building one of the same shape from the images
+    /// of its elements under `f`.
+    fn map_elements<F>(self, f: F) -> T where F: FnMut(Self::Item) -> T::Item;
+}
+
+impl<A> Elements for Vec<A> {
+    type Item = A;
+
+    #[inline]
+    fn for_each_element<F: FnMut(A)>(self, f: F) {
+        self.into_iter().for_each(f)
+    }
+
+    #[inline]
+    fn for_each_element_while<F: FnMut(A) -> bool>(self, f: F) -> bool {
+        self.into_iter().all(f)
+    }
+}
+
+impl<A, B> MapElements<Vec<B>> for Vec<A> {
+    #[inline]
+    fn map_elements<F: FnMut(A) -> B>(self, f: F) -> Vec<B> {
+        self.into_iter().map(f).collect()
+    }
+}
+
+impl<A> Elements for VecDeque<A> {
+    type Item = A;
+
+    #[inline]
+    fn for_each_element<F: FnMut(A)>(self, f: F) {
+        self.into_iter().for_each(f)
+    }
+
+    #[inline]
+    fn for_each_element_while<F: FnMut(A) -> bool>(self, f: F) -> bool {
+        self.into_iter().all(f)
+    }
+}
+
+impl<A, B> MapElements<VecDeque<B>> for VecDeque<A> {
+    #[inline]
+    fn map_elements<F: FnMut(A) -> B>(self, f: F) -> VecDeque<B> {
+        self.into_iter().map(f).collect()
+    }
+}
+
+impl<A> Elements for Box<[A]> {
+    type Item = A;
+
+    #[inline]
+    fn for_each_element<F: FnMut(A)>(self, f: F) {
+        self.into_vec().into_iter().for_each(f)
+    }
+
+    #[inline]
+    fn for_each_element_while<F: FnMut(A) -> bool>(self, f: F) -> bool {
+        self.into_vec().into_iter().all(f)
+    }
+}
+
+impl<A, B> MapElements<Box<[B]>> for Box<[A]> {
+    #[inline]
+    fn map_elements<F: FnMut(A) -> B>(self, f: F) -> Box<[B]> {
+        self.into_vec().into_iter().map(f).collect()
+    }
+}
+
+impl<A, const N: usize> Elements for [A; N] {
+    type Item = A;
+
+    #[inline]
+    fn for_each_element<F: FnMut(A)>(self, f: F) {
+        IntoIterator::into_iter(self).for_each(f)
+    }
+
+    #[inline]
+    fn for_each_element_while<F: FnMut(A) -> bool>(self, f: F) -> bool {
+        IntoIterator::into_iter(self).all(f)
+    }
+}
+
+impl<A, B, const N: usize> MapElements<[B; N]> for [A; N] {
+    #[inline]
+    fn map_elements<F: FnMut(A) -> B>(self, f: F) -> [B; N] {
+        self.map(f)
+    }
+}
+
+impl<A> Elements for Option<A> {
+    type Item = A;
+
+    #[inline]
+    fn for_each_element<F: FnMut(A)>(self, mut f: F) {
+        if let Some(x) = self {
+            f(x)
+        }
+    }
+}
+
+impl<A, B> MapElements<Option<B>> for Option<A> {
+    #[inline]
+    fn map_elements<F: FnMut(A) -> B>(self, f: F) -> Option<B> {
+        self.map(f)
+    }
+}
+
+impl<K, A, S> Elements for HashMap<K, A, S> {
+    type Item = A;
+
+    #[inline]
+    fn for_each_element<F: FnMut(A)>(self, f: F) {
+        self.into_values().for_each(f)
+    }
+
+    #[inline]
+    fn for_each_element_while<F: FnMut(A) -> bool>(self, f: F) -> bool {
+        self.into_values().all(f)
+    }
+}
+
+impl<K, A, B, S> MapElements<HashMap<K, B, S>> for HashMap<K, A, S>
+    where K: Hash + Eq,
+          S: BuildHasher + Default
+{
+    #[inline]
+    fn map_elements<F: FnMut(A) -> B>(self, mut f: F) -> HashMap<K, B, S> {
+        self.into_iter().map(|(k, x)| (k, f(x))).collect()
+    }
+}
+
+impl<K, A> Elements for BTreeMap<K, A> {
+    type Item = A;
+
+    #[inline]
+    fn for_each_element<F: FnMut(A)>(self, f: F) {
+        self.into_values().for_each(f)
+    }
+
+    #[inline]
+    fn for_each_element_while<F: FnMut(A) -> bool>(self, f: F) -> bool {
+        self.into_values().all(f)
+    }
+}
+
+impl<K: Ord, A, B> MapElements<BTreeMap<K, B>> for BTreeMap<K, A> {
+    #[inline]
+    fn map_elements<F: FnMut(A) -> B>(self, mut f: F) -> BTreeMap<K, B> {
+        self.into_iter().map(|(k, x)| (k, f(x))).collect()
+    }
+}
+
+impl<A> Elements for (A, A) {
+    type Item = A;
+
+    #[inline]
+    fn for_each_element<F: FnMut(A)>(self, mut f: F) {
+        f(self.0);
+        f(self.1)
+    }
+}
+
+impl<A, B> MapElements<(B, B)> for (A, A) {
+    #[inline]
+    fn map_elements<F: FnMut(A) -> B>(self, mut f: F) -> (B, B) {
+        (f(self.0), f(self.1))
+    }
+}
+
+impl<A> Elements for (A, A, A) {
+    type Item = A;
+
+    #[inline]
+    fn for_each_element<F: FnMut(A)>(self, mut f: F) {
+        f(self.0);
+        f(self.1);
+        f(self.2)
+    }
+}
+
+impl<A, B> MapElements<(B, B, B)> for (A, A, A) {
+    #[inline]
+    fn map_elements<F: FnMut(A) -> B>(self, mut f: F) -> (B, B, B) {
+        (f(self.0), f(self.1), f(self.2))
+    }
+}
+
+impl<A> Elements for (A, A, A, A) {
+    type Item = A;
+
+    #[inline]
+    fn for_each_element<F: FnMut(A)>(self, mut f: F) {
+        f(self.0);
+        f(self.1);
+        f(self.2);
+        f(self.3)
+    }
+}
+
+impl<A, B> MapElements<(B, B, B, B)> for (A, A, A, A) {
+    #[inline]
+    fn map_elements<F: FnMut(A) -> B>(self, mut f: F) -> (B, B, B, B) {
+        (f(self.0), f(self.1), f(self.2), f(self.3))
+    }
+}
+
+/// A `PartialLens` onto the element at a given position of a sequence.
+pub struct Index<C> {
+    index: usize,
+    phantom_cc: PhantomData<fn(C) -> C>,
+}
+
+impl<C: Ixed<usize>> Index<C> {
+    #[inline]
+    pub fn of(i: usize) -> Self {
+        Index { index: i,
+                phantom_cc: PhantomData, }
+    }
+}
+
+impl<C> Debug for Index<C> {
+    fn fmt(&self, fm: &mut Formatter) -> fmt::Result {
+        fm.debug_struct("Index")
+          .field("index", &self.index)
+          .field("phantom_cc", &self.phantom_cc)
+          .finish()
+    }
+}
+
+impl<C> Clone for Index<C> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> Copy for Index<C> {}
+
+impl<C: Ixed<usize>> Lenticuloid for Index<C> {
+    type InitialSource = C;
+
+    type InitialTarget = C::Item;
+
+    type FinalSource = C;
+
+    type FinalTarget = C::Item;
+
+    type AtInitial = Self;
+
+    fn at_initial(&self) -> Self::AtInitial {
+        *self
+    }
+
+    type AtFinal = Self;
+
+    fn at_final(&self) -> Self::AtFinal {
+        *self
+    }
+}
+
+impl_traversal_via_partial_lens!([C: Ixed<usize>] Index<C>);
+
+impl<C: Ixed<usize>> PartialLens for Index<C> {
+    type Context = C::Hole;
+
+    #[inline]
+    fn try_get(&self, v: C) -> Result<C::Item, C> {
+        v.take_at(&self.index).map(|(x, _)| x)
+    }
+
+    #[inline]
+    fn try_get_context(&self, v: C) -> Result<(C::Item, C::Hole), C> {
+        v.take_at(&self.index)
+    }
+
+    #[inline]
+    fn fill_context(&self, c: C::Hole, x: C::Item) -> C {
+        C::fill_at(c, x)
+    }
+
+    #[inline]
+    fn modify_with<F, X>(&self, v: C, f: F) -> (C, Option<X>)
+        where F: FnOnce(C::Item) -> (C::Item, X)
+    {
+        match v.take_at(&self.index) {
+            Ok((x, hole)) => {
+                let (y, ret) = f(x);
+                (C::fill_at(hole, y), Some(ret))
+            }
+            Err(v) => (v, None),
+        }
+    }
+}
+
+/// A `PartialLens` onto the value stored under a given key of a map.
+pub struct Key<K, M> {
+    key: K,
+    phantom_mm: PhantomData<fn(M) -> M>,
+}
+
+impl<K, M: Ixed<K>> Key<K, M> {
+    #[inline]
+    pub fn of(k: K) -> Self {
+        Key { key: k,
+              phantom_mm: PhantomData, }
+    }
+}
+
+impl<K: Debug, M> Debug for Key<K, M> {
+    fn fmt(&self, fm: &mut Formatter) -> fmt::Result {
+        fm.debug_struct("Key")
+          .field("key", &self.key)
+          .field("phantom_mm", &self.phantom_mm)
+          .finish()
+    }
+}
+
+impl<K: Clone, M> Clone for Key<K, M> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Key { key: self.key.clone(),
+              phantom_mm: PhantomData, }
+    }
+}
+
+impl<K: Copy, M> Copy for Key<K, M> {}
+
+impl<K: Clone, M: Ixed<K>> Lenticuloid for Key<K, M> {
+    type InitialSource = M;
+
+    type InitialTarget = M::Item;
+
+    type FinalSource = M;
+
+    type FinalTarget = M::Item;
+
+    type AtInitial = Self;
+
+    fn at_initial(&self) -> Self::AtInitial {
+        self.clone()
+    }
+
+    type AtFinal = Self;
+
+    fn at_final(&self) -> Self::AtFinal {
+        self.clone()
+    }
+}
+
+impl_traversal_via_partial_lens!([K: Clone, M: Ixed<K>] Key<K, M>);
+
+impl<K: Clone, M: Ixed<K>> PartialLens for Key<K, M> {
+    type Context = M::Hole;
+
+    #[inline]
+    fn try_get(&self, v: M) -> Result<M::Item, M> {
+        v.take_at(&self.key).map(|(x, _)| x)
+    }
+
+    #[inline]
+    fn try_get_context(&self, v: M) -> Result<(M::Item, M::Hole), M> {
+        v.take_at(&self.key)
+    }
+
+    #[inline]
+    fn fill_context(&self, c: M::Hole, x: M::Item) -> M {
+        M::fill_at(c, x)
+    }
+
+    #[inline]
+    fn modify_with<F, X>(&self, v: M, f: F) -> (M, Option<X>)
+        where F: FnOnce(M::Item) -> (M::Item, X)
+    {
+        match v.take_at(&self.key) {
+            Ok((x, hole)) => {
+                let (y, ret) = f(x);
+                (M::fill_at(hole, y), Some(ret))
+            }
+            Err(v) => (v, None),
+        }
+    }
+}
+
+/// A `Lens` onto the (possibly absent) entry under a given key of a map;
+/// setting `Some` inserts or replaces the entry, and setting `None` removes
+/// it.
+pub struct At<K, M> {
+    key: K,
+    phantom_mm: PhantomData<fn(M) -> M>,
+}
+
+impl<K, M: Keyed<K>> At<K, M> {
+    #[inline]
+    pub fn of(k: K) -> Self {
+        At { key: k,
+             phantom_mm: PhantomData, }
+    }
+
+    #[inline]
+    fn put(&self, mut v: M, x: Option<M::Value>) -> M
+        where K: Clone
+    {
+        if let Some(y) = x {
+            v.insert_key(self.key.clone(), y);
+        }
+        v
+    }
+}
+
+impl<K: Debug, M> Debug for At<K, M> {
+    fn fmt(&self, fm: &mut Formatter) -> fmt::Result {
+        fm.debug_struct("At")
+          .field("key", &self.key)
+          .field("phantom_mm", &self.phantom_mm)
+          .finish()
+    }
+}
+
+impl<K: Clone, M> Clone for At<K, M> {
+    #[inline]
+    fn clone(&self) -> Self {
+        At { key: self.key.clone(),
+             phantom_mm: PhantomData, }
+    }
+}
+
+impl<K: Copy, M> Copy for At<K, M> {}
+
+impl<K: Clone, M: Keyed<K>> Lenticuloid for At<K, M> {
+    type InitialSource = M;
+
+    type InitialTarget = Option<M::Value>;
+
+    type FinalSource = M;
+
+    type FinalTarget = Option<M::Value>;
+
+    type AtInitial = Self;
+
+    fn at_initial(&self) -> Self::AtInitial {
+        self.clone()
+    }
+
+    type AtFinal = Self;
+
+    fn at_final(&self) -> Self::AtFinal {
+        self.clone()
+    }
+}
+
+impl_traversal_via_partial_lens!([K: Clone, M: Keyed<K>] At<K, M>);
+
+impl<K: Clone, M: Keyed<K>> PartialLens for At<K, M> {
+    type Context = M;
+
+    #[inline]
+    fn try_get(&self, v: M) -> Result<Option<M::Value>, M> {
+        Ok(self.get(v))
+    }
+
+    #[inline]
+    fn try_get_context(&self, mut v: M) -> Result<(Option<M::Value>, M), M> {
+        let x = v.remove_key(&self.key);
+        Ok((x, v))
+    }
+
+    #[inline]
+    fn fill_context(&self, c: M, x: Option<M::Value>) -> M {
+        self.put(c, x)
+    }
+
+    #[inline]
+    fn set(&self, mut v: M, x: Option<M::Value>) -> M {
+        v.remove_key(&self.key);
+        self.put(v, x)
+    }
+
+    #[inline]
+    fn exchange(&self, mut v: M, x: Option<M::Value>) -> (Option<Option<M::Value>>, M) {
+        let y = v.remove_key(&self.key);
+        (Some(y), self.put(v, x))
+    }
+
+    #[inline]
+    fn modify<F>(&self, mut v: M, f: F) -> M
+        where F: FnOnce(Option<M::Value>) -> Option<M::Value>
+    {
+        let x = v.remove_key(&self.key);
+        self.put(v, f(x))
+    }
+
+    #[inline]
+    fn modify_with<F, X>(&self, mut v: M, f: F) -> (M, Option<X>)
+        where F: FnOnce(Option<M::Value>) -> (Option<M::Value>, X)
+    {
+        let (x, ret) = f(v.remove_key(&self.key));
+        (self.put(v, x), Some(ret))
+    }
+}
+
+impl_getter_via_lens!([K: Clone, M: Keyed<K>] At<K, M>);
+
+impl<K: Clone, M: Keyed<K>> Lens for At<K, M> {
+    #[inline]
+    fn get(&self, mut v: M) -> Option<M::Value> {
+        v.remove_key(&self.key)
+    }
+}
+
+/// A `Lens` onto whether a given element is a member of a set; setting `true`
+/// inserts the element, and setting `false` removes it.
+pub struct Contains<T, C> {
+    member: T,
+    phantom_cc: PhantomData<fn(C) -> C>,
+}
+
+impl<T, C: Membered<T>> Contains<T, C> {
+    #[inline]
+    pub fn of(x: T) -> Self {
+        Contains { member: x,
+                   phantom_cc: PhantomData, }
+    }
+
+    #[inline]
+    fn put(&self, mut v: C, present: bool) -> C
+        where T: Clone
+    {
+        if present {
+            v.insert_member(self.member.clone());
+        }
+        v
+    }
+}
+
+impl<T: Debug, C> Debug for Contains<T, C> {
+    fn fmt(&self, fm: &mut Formatter) -> fmt::Result {
+        fm.debug_struct("Contains")
+          .field("member", &self.member)
+          .field("phantom_cc", &self.phantom_cc)
+          .finish()
+    }
+}
+
+impl<T: Clone, C> Clone for Contains<T, C> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Contains { member: self.member.clone(),
+                   phantom_cc: PhantomData, }
+    }
+}
+
+impl<T: Copy, C> Copy for Contains<T, C> {}
+
+impl<T: Clone, C: Membered<T>> Lenticuloid for Contains<T, C> {
+    type InitialSource = C;
+
+    type InitialTarget = bool;
+
+    type FinalSource = C;
+
+    type FinalTarget = bool;
+
+    type AtInitial = Self;
+
+    fn at_initial(&self) -> Self::AtInitial {
+        self.clone()
+    }
+
+    type AtFinal = Self;
+
+    fn at_final(&self) -> Self::AtFinal {
+        self.clone()
+    }
+}
+
+impl_traversal_via_partial_lens!([T: Clone, C: Membered<T>] Contains<T, C>);
+
+impl<T: Clone, C: Membered<T>> PartialLens for Contains<T, C> {
+    type Context = C;
+
+    #[inline]
+    fn try_get(&self, v: C) -> Result<bool, C> {
+        Ok(self.get(v))
+    }
+
+    #[inline]
+    fn try_get_context(&self, mut v: C) -> Result<(bool, C), C> {
+        let present = v.remove_member(&self.member);
+        Ok((present, v))
+    }
+
+    #[inline]
+    fn fill_context(&self, c: C, x: bool) -> C {
+        self.put(c, x)
+    }
+
+    #[inline]
+    fn set(&self, mut v: C, x: bool) -> C {
+        v.remove_member(&self.member);
+        self.put(v, x)
+    }
+
+    #[inline]
+    fn exchange(&self, mut v: C, x: bool) -> (Option<bool>, C) {
+        let present = v.remove_member(&self.member);
+        (Some(present), self.put(v, x))
+    }
+
+    #[inline]
+    fn modify<F: FnOnce(bool) -> bool>(&self, mut v: C, f: F) -> C {
+        let present = v.remove_member(&self.member);
+        self.put(v, f(present))
+    }
+
+    #[inline]
+    fn modify_with<F, X>(&self, mut v: C, f: F) -> (C, Option<X>)
+        where F: FnOnce(bool) -> (bool, X)
+    {
+        let (x, ret) = f(v.remove_member(&self.member));
+        (self.put(v, x), Some(ret))
+    }
+}
+
+impl_getter_via_lens!([T: Clone, C: Membered<T>] Contains<T, C>);
+
+impl<T: Clone, C: Membered<T>> Lens for Contains<T, C> {
+    #[inline]
+    fn get(&self, mut v: C) -> bool {
+        v.remove_member(&self.member)
+    }
+}
+
+/// A `Prism` splitting a non-empty sequence into its first element and the
+/// rest.
+pub struct Cons<C> {
+    phantom_cc: PhantomData<fn(C) -> C>,
+}
+
+impl<C: Sequence> Cons<C> {
+    #[cfg(not(feature = "nightly"))]
+    #[inline]
+    pub fn mk() -> Self {
+        Cons { phantom_cc: PhantomData }
+    }
+
+    #[cfg(feature = "nightly")]
+    #[inline]
+    pub const fn mk() -> Self {
+        Cons { phantom_cc: PhantomData }
+    }
+}
+
+impl<C> Debug for Cons<C> {
+    fn fmt(&self, fm: &mut Formatter) -> fmt::Result {
+        fm.debug_struct("Cons")
+          .field("phantom_cc", &self.phantom_cc)
+          .finish()
+    }
+}
+
+impl<C> Clone for Cons<C> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> Copy for Cons<C> {}
+
+impl<C: Sequence> Default for Cons<C> {
+    #[inline]
+    fn default() -> Self {
+        Self::mk()
+    }
+}
+
+impl<C: Sequence> Lenticuloid for Cons<C> {
+    type InitialSource = C;
+
+    type InitialTarget = (C::Item, C);
+
+    type FinalSource = C;
+
+    type FinalTarget = (C::Item, C);
+
+    type AtInitial = Self;
+
+    fn at_initial(&self) -> Self::AtInitial {
+        *self
+    }
+
+    type AtFinal = Self;
+
+    fn at_final(&self) -> Self::AtFinal {
+        *self
+    }
+}
+
+impl_traversal_via_partial_lens!([C: Sequence] Cons<C>);
+
+impl<C: Sequence> PartialLens for Cons<C> {
+    type Context = ();
+
+    #[inline]
+    fn try_get(&self, v: C) -> Result<(C::Item, C), C> {
+        v.uncons()
+    }
+
+    #[inline]
+    fn try_get_context(&self, v: C) -> Result<((C::Item, C), ()), C> {
+        v.uncons().map(|p| (p, ()))
+    }
+
+    #[inline]
+    fn fill_context(&self, _c: (), (x, rest): (C::Item, C)) -> C {
+        C::cons(x, rest)
+    }
+
+    #[inline]
+    fn modify_with<F, X>(&self, v: C, f: F) -> (C, Option<X>)
+        where F: FnOnce((C::Item, C)) -> ((C::Item, C), X)
+    {
+        match v.uncons() {
+            Ok(p) => {
+                let ((x, rest), ret) = f(p);
+                (C::cons(x, rest), Some(ret))
+            }
+            Err(v) => (v, None),
+        }
+    }
+}
+
+impl_review_via_prism!([C: Sequence] Cons<C>);
+
+impl<C: Sequence> Prism for Cons<C> {
+    #[inline]
+    fn inject(&self, (x, rest): (C::Item, C)) -> C {
+        C::cons(x, rest)
+    }
+}
+
+/// A `Prism` splitting a non-empty sequence into all but its last element and
+/// the last element.
+pub struct Snoc<C> {
+    phantom_cc: PhantomData<fn(C) -> C>,
+}
+
+impl<C: Sequence> Snoc<C> {
+    #[cfg(not(feature = "nightly"))]
+    #[inline]
+    pub fn mk() -> Self {
+        Snoc { phantom_cc: PhantomData }
+    }
+
+    #[cfg(feature = "nightly")]
+    #[inline]
+    pub const fn mk() -> Self {
+        Snoc { phantom_cc: PhantomData }
+    }
+}
+
+impl<C> Debug for Snoc<C> {
+    fn fmt(&self, fm: &mut Formatter) -> fmt::Result {
+        fm.debug_struct("Snoc")
+          .field("phantom_cc", &self.phantom_cc)
+          .finish()
+    }
+}
+
+impl<C> Clone for Snoc<C> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> Copy for Snoc<C> {}
+
+impl<C: Sequence> Default for Snoc<C> {
+    #[inline]
+    fn default() -> Self {
+        Self::mk()
+    }
+}
+
+impl<C: Sequence> Lenticuloid for Snoc<C> {
+    type InitialSource = C;
+
+    type InitialTarget = (C, C::Item);
+
+    type FinalSource = C;
+
+    type FinalTarget = (C, C::Item);
+
+    type AtInitial = Self;
+
+    fn at_initial(&self) -> Self::AtInitial {
+        *self
+    }
+
+    type AtFinal = Self;
+
+    fn at_final(&self) -> Self::AtFinal {
+        *self
+    }
+}
+
+impl_traversal_via_partial_lens!([C: Sequence] Snoc<C>);
+
+impl<C: Sequence> PartialLens for Snoc<C> {
+    type Context = ();
+
+    #[inline]
+    fn try_get(&self, v: C) -> Result<(C, C::Item), C> {
+        v.unsnoc()
+    }
+
+    #[inline]
+    fn try_get_context(&self, v: C) -> Result<((C, C::Item), ()), C> {
+        v.unsnoc().map(|p| (p, ()))
+    }
+
+    #[inline]
+    fn fill_context(&self, _c: (), (init, x): (C, C::Item)) -> C {
+        C::snoc(init, x)
+    }
+
+    #[inline]
+    fn modify_with<F, X>(&self, v: C, f: F) -> (C, Option<X>)
+        where F: FnOnce((C, C::Item)) -> ((C, C::Item), X)
+    {
+        match v.unsnoc() {
+            Ok(p) => {
+                let ((init, x), ret) = f(p);
+                (C::snoc(init, x), Some(ret))
+            }
+            Err(v) => (v, None),
+        }
+    }
+}
+
+impl_review_via_prism!([C: Sequence] Snoc<C>);
+
+impl<C: Sequence> Prism for Snoc<C> {
+    #[inline]
+    fn inject(&self, (init, x): (C, C::Item)) -> C {
+        C::snoc(init, x)
+    }
+}
+
+/// A `PartialLens` onto the first element of a sequence.
+pub struct First<C> {
+    phantom_cc: PhantomData<fn(C) -> C>,
+}
+
+impl<C: Sequence> First<C> {
+    #[cfg(not(feature = "nightly"))]
+    #[inline]
+    pub fn mk() -> Self {
+        First { phantom_cc: PhantomData }
+    }
+
+    #[cfg(feature = "nightly")]
+    #[inline]
+    pub const fn mk() -> Self {
+        First { phantom_cc: PhantomData }
+    }
+}
+
+impl<C> Debug for First<C> {
+    fn fmt(&self, fm: &mut Formatter) -> fmt::Result {
+        fm.debug_struct("First")
+          .field("phantom_cc", &self.phantom_cc)
+          .finish()
+    }
+}
+
+impl<C> Clone for First<C> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> Copy for First<C> {}
+
+impl<C: Sequence> Default for First<C> {
+    #[inline]
+    fn default() -> Self {
+        Self::mk()
+    }
+}
+
+impl<C: Sequence> Lenticuloid for First<C> {
+    type InitialSource = C;
+
+    type InitialTarget = C::Item;
+
+    type FinalSource = C;
+
+    type FinalTarget = C::Item;
+
+    type AtInitial = Self;
+
+    fn at_initial(&self) -> Self::AtInitial {
+        *self
+    }
+
+    type AtFinal = Self;
+
+    fn at_final(&self) -> Self::AtFinal {
+        *self
+    }
+}
+
+impl_traversal_via_partial_lens!([C: Sequence] First<C>);
+
+impl<C: Sequence> PartialLens for First<C> {
+    type Context = C;
+
+    #[inline]
+    fn try_get(&self, v: C) -> Result<C::Item, C> {
+        v.uncons().map(|(x, _)| x)
+    }
+
+    #[inline]
+    fn try_get_context(&self, v: C) -> Result<(C::Item, C), C> {
+        v.uncons()
+    }
+
+    #[inline]
+    fn fill_context(&self, c: C, x: C::Item) -> C {
+        C::cons(x, c)
+    }
+
+    #[inline]
+    fn modify_with<F, X>(&self, v: C, f: F) -> (C, Option<X>)
+        where F: FnOnce(C::Item) -> (C::Item, X)
+    {
+        match v.uncons() {
+            Ok((x, rest)) => {
+                let (y, ret) = f(x);
+                (C::cons(y, rest), Some(ret))
+            }
+            Err(v) => (v, None),
+        }
+    }
+}
+
+/// A `PartialLens` onto the last element of a sequence.
+pub struct Last<C> {
+    phantom_cc: PhantomData<fn(C) -> C>,
+}
+
+impl<C: Sequence> Last<C> {
+    #[cfg(not(feature = "nightly"))]
+    #[inline]
+    pub fn mk() -> Self {
+        Last { phantom_cc: PhantomData }
+    }
+
+    #[cfg(feature = "nightly")]
+    #[inline]
+    pub const fn mk() -> Self {
+        Last { phantom_cc: PhantomData }
+    }
+}
+
+impl<C> Debug for Last<C> {
+    fn fmt(&self, fm: &mut Formatter) -> fmt::Result {
+        fm.debug_struct("Last")
+          .field("phantom_cc", &self.phantom_cc)
+          .finish()
+    }
+}
+
+impl<C> Clone for Last<C> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> Copy for Last<C> {}
+
+impl<C: Sequence> Default for Last<C> {
+    #[inline]
+    fn default() -> Self {
+        Self::mk()
+    }
+}
+
+impl<C: Sequence> Lenticuloid for Last<C> {
+    type InitialSource = C;
+
+    type InitialTarget = C::Item;
+
+    type FinalSource = C;
+
+    type FinalTarget = C::Item;
+
+    type AtInitial = Self;
+
+    fn at_initial(&self) -> Self::AtInitial {
+        *self
+    }
+
+    type AtFinal = Self;
+
+    fn at_final(&self) -> Self::AtFinal {
+        *self
+    }
+}
+
+impl_traversal_via_partial_lens!([C: Sequence] Last<C>);
+
+impl<C: Sequence> PartialLens for Last<C> {
+    type Context = C;
+
+    #[inline]
+    fn try_get(&self, v: C) -> Result<C::Item, C> {
+        v.unsnoc().map(|(_, x)| x)
+    }
+
+    #[inline]
+    fn try_get_context(&self, v: C) -> Result<(C::Item, C), C> {
+        v.unsnoc().map(|(init, x)| (x, init))
+    }
+
+    #[inline]
+    fn fill_context(&self, c: C, x: C::Item) -> C {
+        C::snoc(c, x)
+    }
+
+    #[inline]
+    fn modify_with<F, X>(&self, v: C, f: F) -> (C, Option<X>)
+        where F: FnOnce(C::Item) -> (C::Item, X)
+    {
+        match v.unsnoc() {
+            Ok((init, x)) => {
+                let (y, ret) = f(x);
+                (C::snoc(init, y), Some(ret))
+            }
+            Err(v) => (v, None),
+        }
+    }
+}
+
+/// A `Traversal` family focusing on every element of a collection.
+pub struct Each<S, T = S> {
+    phantom_st: PhantomData<fn(S) -> T>,
+}
+
+impl<S, T> Each<S, T>
+    where S: MapElements<T> + MapElements<S>,
+          T: MapElements<T>
+{
+    #[cfg(not(feature = "nightly"))]
+    #[inline]
+    pub fn mk() -> Self {
+        Each { phantom_st: PhantomData }
+    }
+
+    #[cfg(feature = "nightly")]
+    #[inline]
+    pub const fn mk() -> Self {
+        Each { phantom_st: PhantomData }
+    }
+}
+
+impl<S, T> Debug for Each<S, T> {
+    fn fmt(&self, fm: &mut Formatter) -> fmt::Result {
+        fm.debug_struct("Each")
+          .field("phantom_st", &self.phantom_st)
+          .finish()
+    }
+}
+
+impl<S, T> Clone for Each<S, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S, T> Copy for Each<S, T> {}
+
+impl<S, T> Default for Each<S, T>
+    where S: MapElements<T> + MapElements<S>,
+          T: MapElements<T>
+{
+    #[inline]
+    fn default() -> Self {
+        Self::mk()
+    }
+}
+
+impl<S, T> Lenticuloid for Each<S, T>
+    where S: MapElements<T> + MapElements<S>,
+          T: MapElements<T>
+{
+    type InitialSource = S;
+
+    type InitialTarget = S::Item;
+
+    type FinalSource = T;
+
+    type FinalTarget = T::Item;
+
+    type AtInitial = Each<S, S>;
+
+    fn at_initial(&self) -> Self::AtInitial {
+        Each::mk()
+    }
+
+    type AtFinal = Each<T, T>;
+
+    fn at_final(&self) -> Self::AtFinal {
+        Each::mk()
+    }
+}
+
+impl<S, T> Fold for Each<S, T>
+    where S: MapElements<T> + MapElements<S>,
+          T: MapElements<T>
+{
+    #[inline]
+    fn for_each<F: FnMut(S::Item)>(&self, v: S, f: F) {
+        v.for_each_element(f)
+    }
+
+    #[inline]
+    fn for_each_while<F: FnMut(S::Item) -> bool>(&self, v: S, f: F) -> bool {
+        v.for_each_element_while(f)
+    }
+}
+
+impl<S, T> Setter for Each<S, T>
+    where S: MapElements<T> + MapElements<S>,
+          T: MapElements<T>
+{
+    #[inline]
+    fn over<F>(&self, v: S, f: F) -> T
+        where F: FnMut(S::Item) -> T::Item
+    {
+        <S as MapElements<T>>::map_elements(v, f)
+    }
+}
+
+impl<S, T> Traversal for Each<S, T>
+    where S: MapElements<T> + MapElements<S>,
+          T: MapElements<T>
+{
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::{ComposeExt, Lens, PartialLens, Prism, Traversal};
+    use errors::Some_;
+
+    #[test]
+    fn test_index() {
+        let l = Index::<Vec<u32>>::of(1);
+        assert_eq!(l.set(vec![1, 2, 3], 5), vec![1, 5, 3]);
+        assert_eq!(l.set(vec![1], 5), vec![1]);
+        assert_eq!(l.try_get(vec![4, 5, 6]), Ok(5));
+        let l = Index::<[u32; 3]>::of(2);
+        assert_eq!(l.modify([1, 2, 3], |x| x * 10), [1, 2, 30]);
+        let l = Index::<VecDeque<u32>>::of(0);
+        let (x, mut inj) = l.try_get_inject(vec![7, 8, 9].into_iter().collect()).ok().unwrap();
+        assert_eq!(x, 7);
+        assert_eq!(inj(0).unwrap(), vec![0, 8, 9]);
+    }
+
+    #[test]
+    fn test_key_nested() {
+        let mut inner = HashMap::new();
+        inner.insert("b".to_string(), vec![1, 2]);
+        let mut outer = BTreeMap::new();
+        outer.insert(1, inner);
+        let l = Index::<Vec<u32>>::of(1)
+                    .compose(Key::<String, HashMap<String, Vec<u32>>>::of("b".to_string()))
+                    .compose(Key::<u32, BTreeMap<u32, _>>::of(1));
+        let outer = l.modify(outer, |x| x + 1);
+        assert_eq!(outer[&1]["b"], vec![1, 3]);
+        let outer = l.set(outer, 0);
+        assert_eq!(outer[&1]["b"], vec![1, 0]);
+        let l = Key::<u32, BTreeMap<u32, HashMap<String, Vec<u32>>>>::of(2);
+        assert_eq!(l.set(outer.clone(), HashMap::new()), outer);
+    }
+
+    #[test]
+    fn test_at() {
+        let l = At::<&str, HashMap<&str, u32>>::of("a");
+        let m = l.set(HashMap::new(), Some(1));
+        assert_eq!(l.get(m.clone()), Some(1));
+        let p = Some_::<u32>::mk().compose(l);
+        let m = p.modify(m, |x| x + 1);
+        assert_eq!(m["a"], 2);
+        assert!(p.modify(HashMap::new(), |x| x + 1).is_empty());
+        assert!(l.set(m, None).is_empty());
+    }
+
+    #[test]
+    fn test_contains() {
+        struct Registry {
+            flags: HashSet<String>,
+        }
+        let l = Contains::<String, HashSet<String>>::of("fast".to_string())
+                    .compose(field_lens!(Registry => flags: HashSet<String>));
+        let r = l.set(Registry { flags: HashSet::new() }, true);
+        assert!(r.flags.contains("fast"));
+        let r = l.modify(r, |x| !x);
+        assert!(r.flags.is_empty());
+        let s: BTreeSet<u32> = vec![1, 2].into_iter().collect();
+        assert!(Contains::<u32, BTreeSet<u32>>::of(2).get(s.clone()));
+        assert!(!Contains::<u32, BTreeSet<u32>>::of(3).get(s));
+    }
+
+    #[test]
+    fn test_sequence_prisms() {
+        let l = Cons::<Vec<u32>>::mk();
+        assert_eq!(l.try_get(vec![1, 2, 3]), Ok((1, vec![2, 3])));
+        assert_eq!(l.try_get(vec![]), Err(vec![]));
+        assert_eq!(l.inject((0, vec![1])), vec![0, 1]);
+        let l = Snoc::<String>::mk();
+        assert_eq!(l.try_get("abc".to_string()), Ok(("ab".to_string(), 'c')));
+        let l = First::<VecDeque<u32>>::mk();
+        let v: VecDeque<u32> = l.modify(vec![1, 2].into_iter().collect(), |x| x + 10);
+        assert_eq!(v, vec![11, 2]);
+        let l = Last::<Box<[u32]>>::mk();
+        assert_eq!(&*l.set(vec![1, 2].into_boxed_slice(), 5), &[1, 5]);
+        assert_eq!(Last::<String>::mk().try_get(String::new()), Err(String::new()));
+    }
+
+    #[test]
+    fn test_each() {
+        struct Person {
+            name: String,
+        }
+        let l = field_lens!(Person => name: String).compose(Each::<Vec<Person>>::mk())
+                                                   .compose(Each::<Vec<Vec<Person>>>::mk());
+        let people = vec![vec![Person { name: "ann".to_string() }],
+                          vec![Person { name: "bob".to_string() },
+                               Person { name: "cy".to_string() }]];
+        let people = l.modify_all(people, |x| x.to_uppercase());
+        assert_eq!(l.to_vec(people), vec!["ANN", "BOB", "CY"]);
+        let l = Each::<[u32; 3], [String; 3]>::mk();
+        assert_eq!(l.modify_all([1, 2, 3], |x| x.to_string()), ["1", "2", "3"]);
+        let l = Each::<BTreeMap<&str, u32>>::mk();
+        let m: BTreeMap<&str, u32> = vec![("a", 1), ("b", 2)].into_iter().collect();
+        assert_eq!(l.to_vec(l.set_all(m, 0)), vec![0, 0]);
+        assert_eq!(Each::<(u32, u32)>::mk().modify_all((1, 2), |x| x + 1), (2, 3));
+    }
+
+    #[test]
+    fn test_each_collections() {
+        let l = Each::<VecDeque<u32>>::mk();
+        let v: VecDeque<u32> = l.modify_all(vec![1, 2].into_iter().collect(), |x| x * 2);
+        assert_eq!(l.to_vec(v), vec![2, 4]);
+        let l = Each::<Box<[u32]>, Box<[String]>>::mk();
+        let v = l.modify_all(vec![1, 2].into_boxed_slice(), |x| x.to_string());
+        assert_eq!(Each::<Box<[String]>>::mk().to_vec(v), vec!["1", "2"]);
+        let l = Each::<Option<u32>>::mk();
+        assert_eq!(l.modify_all(Some(1), |x| x + 1), Some(2));
+        assert_eq!(l.modify_all(None, |x| x + 1), None);
+        assert_eq!(l.to_vec(Some(3)), vec![3]);
+        assert!(l.to_vec(None).is_empty());
+        let l = Each::<HashMap<&str, u32>>::mk();
+        let m: HashMap<&str, u32> = vec![("a", 1), ("b", 2), ("c", 3)].into_iter().collect();
+        let m = l.modify_all(m, |x| x * 10);
+        assert_eq!(m["b"], 20);
+        let mut xs = l.to_vec(m);
+        xs.sort();
+        assert_eq!(xs, vec![10, 20, 30]);
+        let l = Each::<BTreeMap<&str, u32>, BTreeMap<&str, String>>::mk();
+        let m: BTreeMap<&str, u32> = vec![("b", 2), ("a", 1)].into_iter().collect();
+        let m = l.modify_all(m, |x| x.to_string());
+        assert_eq!(m["a"], "1");
+        assert_eq!(Each::<BTreeMap<&str, String>>::mk().to_vec(m), vec!["1", "2"]);
+    }
+}
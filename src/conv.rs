@@ -3,12 +3,12 @@
 
 use std::fmt::{self, Debug, Formatter};
 use std::marker::PhantomData;
-use super::{Injector, Iso, Lens, Lenticuloid, PartialLens, Prism, util};
+use super::{Iso, Lens, Lenticuloid, PartialLens, Prism};
 
 /// An isomorphism family that handles lossless conversions by owned value.
 pub struct Conv<S, A = S, T = S, B = A> {
-    phantom_sa: PhantomData<Fn(S) -> A>,
-    phantom_bt: PhantomData<Fn(B) -> T>,
+    phantom_sa: PhantomData<fn(S) -> A>,
+    phantom_bt: PhantomData<fn(B) -> T>,
 }
 
 impl<S, A, T, B> Conv<S, A, T, B>
@@ -110,25 +110,35 @@ impl<S, A, T, B> Lenticuloid for Conv<S, A, T, B>
     }
 }
 
+impl_traversal_via_partial_lens!([S, A, T, B] Conv<S, A, T, B>
+                                 where [S: Into<A>,
+                                        A: Into<S>,
+                                        B: Into<T>,
+                                        T: Into<B>]);
+
 impl<S, A, T, B> PartialLens for Conv<S, A, T, B>
     where S: Into<A>,
           A: Into<S>,
           B: Into<T>,
           T: Into<B>
 {
+    type Context = ();
+
     #[inline]
     fn try_get(&self, v: Self::InitialSource) -> Result<Self::InitialTarget, Self::FinalSource> {
         Ok(v.into())
     }
 
     #[inline]
-    fn try_get_inject(&self,
-                      v: Self::InitialSource)
-                      -> Result<(Self::InitialTarget,
-                                 Injector<Self::FinalTarget, Self::FinalSource>),
-                                Self::FinalSource> {
-        Ok((v.into(),
-            util::once_to_mut(move |x: Self::FinalTarget| -> Self::FinalSource { x.into() })))
+    fn try_get_context(&self,
+                       v: Self::InitialSource)
+                       -> Result<(Self::InitialTarget, ()), Self::FinalSource> {
+        Ok((v.into(), ()))
+    }
+
+    #[inline]
+    fn fill_context(&self, _c: (), x: Self::FinalTarget) -> Self::FinalSource {
+        x.into()
     }
 
     #[inline]
@@ -160,6 +170,12 @@ impl<S, A, T, B> PartialLens for Conv<S, A, T, B>
     }
 }
 
+impl_getter_via_lens!([S, A, T, B] Conv<S, A, T, B>
+                      where [S: Into<A>,
+                             A: Into<S>,
+                             B: Into<T>,
+                             T: Into<B>]);
+
 impl<S, A, T, B> Lens for Conv<S, A, T, B>
     where S: Into<A>,
           A: Into<S>,
@@ -172,6 +188,12 @@ impl<S, A, T, B> Lens for Conv<S, A, T, B>
     }
 }
 
+impl_review_via_prism!([S, A, T, B] Conv<S, A, T, B>
+                       where [S: Into<A>,
+                              A: Into<S>,
+                              B: Into<T>,
+                              T: Into<B>]);
+
 impl<S, A, T, B> Prism for Conv<S, A, T, B>
     where S: Into<A>,
           A: Into<S>,
@@ -194,8 +216,8 @@ impl<S, A, T, B> Iso for Conv<S, A, T, B>
 
 /// An isomorphism family that handles lossless conversions by shared reference.
 pub struct ConvRef<'a, S: ?Sized + 'a, A: ?Sized + 'a = S, T: ?Sized + 'a = S, B: ?Sized + 'a = T> {
-    phantom_sa: PhantomData<Fn(&'a S) -> &'a A>,
-    phantom_bt: PhantomData<Fn(&'a B) -> &'a T>,
+    phantom_sa: PhantomData<fn(&'a S) -> &'a A>,
+    phantom_bt: PhantomData<fn(&'a B) -> &'a T>,
 }
 
 impl<'a, S: ?Sized, A: ?Sized, T: ?Sized, B: ?Sized> ConvRef<'a, S, A, T, B>
@@ -297,25 +319,37 @@ impl<'a, S: ?Sized, A: ?Sized, T: ?Sized, B: ?Sized> Lenticuloid for ConvRef<'a,
     }
 }
 
+impl_traversal_via_partial_lens! {
+    ['a, S: ?Sized, A: ?Sized, T: ?Sized, B: ?Sized] ConvRef<'a, S, A, T, B>
+    where [S: AsRef<A> + 'a,
+           A: AsRef<S> + 'a,
+           T: AsRef<B> + 'a,
+           B: AsRef<T> + 'a]
+}
+
 impl<'a, S: ?Sized, A: ?Sized, T: ?Sized, B: ?Sized> PartialLens for ConvRef<'a, S, A, T, B>
     where S: AsRef<A> + 'a,
           A: AsRef<S> + 'a,
           T: AsRef<B> + 'a,
           B: AsRef<T> + 'a
 {
+    type Context = ();
+
     #[inline]
     fn try_get(&self, v: Self::InitialSource) -> Result<Self::InitialTarget, Self::FinalSource> {
         Ok(v.as_ref())
     }
 
     #[inline]
-    fn try_get_inject(&self,
-                      v: Self::InitialSource)
-                      -> Result<(Self::InitialTarget,
-                                 Injector<Self::FinalTarget, Self::FinalSource>),
-                                Self::FinalSource> {
-        Ok((v.as_ref(),
-            util::once_to_mut(move |x: Self::FinalTarget| -> Self::FinalSource { x.as_ref() })))
+    fn try_get_context(&self,
+                       v: Self::InitialSource)
+                       -> Result<(Self::InitialTarget, ()), Self::FinalSource> {
+        Ok((v.as_ref(), ()))
+    }
+
+    #[inline]
+    fn fill_context(&self, _c: (), x: Self::FinalTarget) -> Self::FinalSource {
+        x.as_ref()
     }
 
     #[inline]
@@ -346,6 +380,12 @@ impl<'a, S: ?Sized, A: ?Sized, T: ?Sized, B: ?Sized> PartialLens for ConvRef<'a,
     }
 }
 
+impl_getter_via_lens!(['a, S: ?Sized, A: ?Sized, T: ?Sized, B: ?Sized] ConvRef<'a, S, A, T, B>
+                      where [S: AsRef<A> + 'a,
+                             A: AsRef<S> + 'a,
+                             T: AsRef<B> + 'a,
+                             B: AsRef<T> + 'a]);
+
 impl<'a, S: ?Sized, A: ?Sized, T: ?Sized, B: ?Sized> Lens for ConvRef<'a, S, A, T, B>
     where S: AsRef<A> + 'a,
           A: AsRef<S> + 'a,
@@ -358,6 +398,12 @@ impl<'a, S: ?Sized, A: ?Sized, T: ?Sized, B: ?Sized> Lens for ConvRef<'a, S, A,
     }
 }
 
+impl_review_via_prism!(['a, S: ?Sized, A: ?Sized, T: ?Sized, B: ?Sized] ConvRef<'a, S, A, T, B>
+                       where [S: AsRef<A> + 'a,
+                              A: AsRef<S> + 'a,
+                              T: AsRef<B> + 'a,
+                              B: AsRef<T> + 'a]);
+
 impl<'a, S: ?Sized, A: ?Sized, T: ?Sized, B: ?Sized> Prism for ConvRef<'a, S, A, T, B>
     where S: AsRef<A> + 'a,
           A: AsRef<S> + 'a,
@@ -381,8 +427,8 @@ impl<'a, S: ?Sized, A: ?Sized, T: ?Sized, B: ?Sized> Iso for ConvRef<'a, S, A, T
 /// An isomorphism family that handles lossless conversions by mutable
 /// reference.
 pub struct ConvMut<'a, S: ?Sized + 'a, A: ?Sized + 'a = S, T: ?Sized + 'a = S, B: ?Sized + 'a = T> {
-    phantom_sa: PhantomData<Fn(&'a mut S) -> &'a mut A>,
-    phantom_bt: PhantomData<Fn(&'a mut B) -> &'a mut T>,
+    phantom_sa: PhantomData<fn(&'a mut S) -> &'a mut A>,
+    phantom_bt: PhantomData<fn(&'a mut B) -> &'a mut T>,
 }
 
 impl<'a, S: ?Sized, A: ?Sized, T: ?Sized, B: ?Sized> ConvMut<'a, S, A, T, B>
@@ -484,25 +530,37 @@ impl<'a, S: ?Sized, A: ?Sized, T: ?Sized, B: ?Sized> Lenticuloid for ConvMut<'a,
     }
 }
 
+impl_traversal_via_partial_lens! {
+    ['a, S: ?Sized, A: ?Sized, T: ?Sized, B: ?Sized] ConvMut<'a, S, A, T, B>
+    where [S: AsMut<A> + 'a,
+           A: AsMut<S> + 'a,
+           T: AsMut<B> + 'a,
+           B: AsMut<T> + 'a]
+}
+
 impl<'a, S: ?Sized, A: ?Sized, T: ?Sized, B: ?Sized> PartialLens for ConvMut<'a, S, A, T, B>
     where S: AsMut<A> + 'a,
           A: AsMut<S> + 'a,
           T: AsMut<B> + 'a,
           B: AsMut<T> + 'a
 {
+    type Context = ();
+
     #[inline]
     fn try_get(&self, v: Self::InitialSource) -> Result<Self::InitialTarget, Self::FinalSource> {
         Ok(v.as_mut())
     }
 
     #[inline]
-    fn try_get_inject(&self,
-                      v: Self::InitialSource)
-                      -> Result<(Self::InitialTarget,
-                                 Injector<Self::FinalTarget, Self::FinalSource>),
-                                Self::FinalSource> {
-        Ok((v.as_mut(),
-            util::once_to_mut(move |x: Self::FinalTarget| -> Self::FinalSource { x.as_mut() })))
+    fn try_get_context(&self,
+                       v: Self::InitialSource)
+                       -> Result<(Self::InitialTarget, ()), Self::FinalSource> {
+        Ok((v.as_mut(), ()))
+    }
+
+    #[inline]
+    fn fill_context(&self, _c: (), x: Self::FinalTarget) -> Self::FinalSource {
+        x.as_mut()
     }
 
     #[inline]
@@ -534,6 +592,12 @@ impl<'a, S: ?Sized, A: ?Sized, T: ?Sized, B: ?Sized> PartialLens for ConvMut<'a,
     }
 }
 
+impl_getter_via_lens!(['a, S: ?Sized, A: ?Sized, T: ?Sized, B: ?Sized] ConvMut<'a, S, A, T, B>
+                      where [S: AsMut<A> + 'a,
+                             A: AsMut<S> + 'a,
+                             T: AsMut<B> + 'a,
+                             B: AsMut<T> + 'a]);
+
 impl<'a, S: ?Sized, A: ?Sized, T: ?Sized, B: ?Sized> Lens for ConvMut<'a, S, A, T, B>
     where S: AsMut<A> + 'a,
           A: AsMut<S> + 'a,
@@ -546,6 +610,12 @@ impl<'a, S: ?Sized, A: ?Sized, T: ?Sized, B: ?Sized> Lens for ConvMut<'a, S, A,
     }
 }
 
+impl_review_via_prism!(['a, S: ?Sized, A: ?Sized, T: ?Sized, B: ?Sized] ConvMut<'a, S, A, T, B>
+                       where [S: AsMut<A> + 'a,
+                              A: AsMut<S> + 'a,
+                              T: AsMut<B> + 'a,
+                              B: AsMut<T> + 'a]);
+
 impl<'a, S: ?Sized, A: ?Sized, T: ?Sized, B: ?Sized> Prism for ConvMut<'a, S, A, T, B>
     where S: AsMut<A> + 'a,
           A: AsMut<S> + 'a,
@@ -0,0 +1,805 @@
+//! These lenticuloids address the insides of dynamically-typed, JSON-like
+//! trees of `Value`s, or of anything else that is a `Tree`, by key, by index,
+//! or by a whole `Path` parsed from a string like `servers[2].ports.http`.
+
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::mem;
+use std::str::FromStr;
+use super::erased::DynPartialLens;
+use super::{Compose, Identity, Lenticuloid, PartialLens};
+
+/// A dynamically-typed, JSON-like value.
+#[derive(Clone,Debug,PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(BTreeMap<String, Value>),
+}
+
+impl Default for Value {
+    #[inline]
+    fn default() -> Self {
+        Value::Null
+    }
+}
+
+impl From<bool> for Value {
+    #[inline]
+    fn from(b: bool) -> Self {
+        Value::Bool(b)
+    }
+}
+
+impl From<f64> for Value {
+    #[inline]
+    fn from(n: f64) -> Self {
+        Value::Number(n)
+    }
+}
+
+impl From<String> for Value {
+    #[inline]
+    fn from(s: String) -> Self {
+        Value::String(s)
+    }
+}
+
+impl<'a> From<&'a str> for Value {
+    #[inline]
+    fn from(s: &'a str) -> Self {
+        Value::String(s.to_owned())
+    }
+}
+
+impl From<Vec<Value>> for Value {
+    #[inline]
+    fn from(v: Vec<Value>) -> Self {
+        Value::Array(v)
+    }
+}
+
+impl From<BTreeMap<String, Value>> for Value {
+    #[inline]
+    fn from(m: BTreeMap<String, Value>) -> Self {
+        Value::Object(m)
+    }
+}
+
+/// A JSON-like value that `Key`, `Index` and `Path` can look inside, because
+/// it may be an object with members by key or an array with elements by
+/// index.
+pub trait Tree: Default + 'static {
+    fn member(&self, key: &str) -> Option<&Self>;
+
+    fn member_mut(&mut self, key: &str) -> Option<&mut Self>;
+
+    /// Take a member out of an object, leaving the rest of the object behind.
+    fn remove_member(&mut self, key: &str) -> Option<Self>;
+
+    /// Put a member into an object; anything but an object is left alone.
+    fn insert_member(&mut self, key: String, x: Self);
+
+    fn element(&self, index: usize) -> Option<&Self>;
+
+    fn element_mut(&mut self, index: usize) -> Option<&mut Self>;
+}
+
+/// Implement `Tree` for a JSON-like `$value` enum, whose `Object` variant
+/// holds a map from `String`s and whose `Array` variant holds a `Vec`.
+macro_rules! impl_tree {
+    ($value:ident) => {
+        impl $crate::dynamic::Tree for $value {
+            #[inline]
+            fn member(&self, key: &str) -> Option<&Self> {
+                match *self {
+                    $value::Object(ref m) => m.get(key),
+                    _ => None,
+                }
+            }
+
+            #[inline]
+            fn member_mut(&mut self, key: &str) -> Option<&mut Self> {
+                match *self {
+                    $value::Object(ref mut m) => m.get_mut(key),
+                    _ => None,
+                }
+            }
+
+            #[inline]
+            fn remove_member(&mut self, key: &str) -> Option<Self> {
+                match *self {
+                    $value::Object(ref mut m) => m.remove(key),
+                    _ => None,
+                }
+            }
+
+            #[inline]
+            fn insert_member(&mut self, key: String, x: Self) {
+                if let $value::Object(ref mut m) = *self {
+                    m.insert(key, x);
+                }
+            }
+
+            #[inline]
+            fn element(&self, index: usize) -> Option<&Self> {
+                match *self {
+                    $value::Array(ref a) => a.get(index),
+                    _ => None,
+                }
+            }
+
+            #[inline]
+            fn element_mut(&mut self, index: usize) -> Option<&mut Self> {
+                match *self {
+                    $value::Array(ref mut a) => a.get_mut(index),
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+impl_tree!(Value);
+
+/// A partial lens onto the member of an object with a given key.
+pub struct Key<V = Value> {
+    key: String,
+    phantom_vv: PhantomData<fn(V) -> V>,
+}
+
+impl<V> Debug for Key<V> {
+    fn fmt(&self, fm: &mut Formatter) -> fmt::Result {
+        fm.debug_struct("Key")
+          .field("key", &self.key)
+          .finish()
+    }
+}
+
+impl<V> Clone for Key<V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Key::of(self.key.as_str())
+    }
+}
+
+impl<V> PartialEq for Key<V> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<V> Eq for Key<V> {}
+
+impl<V> Hash for Key<V> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key.hash(state)
+    }
+}
+
+impl<V> Key<V> {
+    #[inline]
+    pub fn of<K: Into<String>>(key: K) -> Self {
+        Key { key: key.into(),
+              phantom_vv: PhantomData, }
+    }
+}
+
+impl<V: Tree> Lenticuloid for Key<V> {
+    type InitialSource = V;
+
+    type InitialTarget = V;
+
+    type FinalSource = V;
+
+    type FinalTarget = V;
+
+    type AtInitial = Self;
+
+    fn at_initial(&self) -> Self::AtInitial {
+        self.clone()
+    }
+
+    type AtFinal = Self;
+
+    fn at_final(&self) -> Self::AtFinal {
+        self.clone()
+    }
+}
+
+impl_traversal_via_partial_lens!([V: Tree] Key<V>);
+
+impl<V: Tree> PartialLens for Key<V> {
+    /// The object, less the member.
+    type Context = V;
+
+    fn try_get_context(&self, mut v: V) -> Result<(V, V), V> {
+        match v.remove_member(&self.key) {
+            Some(x) => Ok((x, v)),
+            None => Err(v),
+        }
+    }
+
+    #[inline]
+    fn fill_context(&self, mut c: V, x: V) -> V {
+        c.insert_member(self.key.clone(), x);
+        c
+    }
+
+    fn modify_with<F, X>(&self, mut v: V, f: F) -> (V, Option<X>)
+        where F: FnOnce(V) -> (V, X)
+    {
+        let aux = v.member_mut(&self.key).map(|slot| {
+            let (x, aux) = f(mem::take(slot));
+            *slot = x;
+            aux
+        });
+        (v, aux)
+    }
+}
+
+/// A partial lens onto the element of an array at a given index.
+pub struct Index<V = Value> {
+    index: usize,
+    phantom_vv: PhantomData<fn(V) -> V>,
+}
+
+impl<V> Debug for Index<V> {
+    fn fmt(&self, fm: &mut Formatter) -> fmt::Result {
+        fm.debug_struct("Index")
+          .field("index", &self.index)
+          .finish()
+    }
+}
+
+impl<V> Clone for Index<V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<V> Copy for Index<V> {}
+
+impl<V> Default for Index<V> {
+    #[inline]
+    fn default() -> Self {
+        Index::of(0)
+    }
+}
+
+impl<V> PartialEq for Index<V> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+impl<V> Eq for Index<V> {}
+
+impl<V> Hash for Index<V> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.index.hash(state)
+    }
+}
+
+impl<V> Index<V> {
+    #[inline]
+    pub fn of(index: usize) -> Self {
+        Index { index,
+                phantom_vv: PhantomData, }
+    }
+}
+
+impl<V: Tree> Lenticuloid for Index<V> {
+    type InitialSource = V;
+
+    type InitialTarget = V;
+
+    type FinalSource = V;
+
+    type FinalTarget = V;
+
+    type AtInitial = Self;
+
+    fn at_initial(&self) -> Self::AtInitial {
+        *self
+    }
+
+    type AtFinal = Self;
+
+    fn at_final(&self) -> Self::AtFinal {
+        *self
+    }
+}
+
+impl_traversal_via_partial_lens!([V: Tree] Index<V>);
+
+impl<V: Tree> PartialLens for Index<V> {
+    /// The array, with a placeholder where the element was.
+    type Context = V;
+
+    fn try_get_context(&self, mut v: V) -> Result<(V, V), V> {
+        match v.element_mut(self.index).map(mem::take) {
+            Some(x) => Ok((x, v)),
+            None => Err(v),
+        }
+    }
+
+    #[inline]
+    fn fill_context(&self, mut c: V, x: V) -> V {
+        if let Some(slot) = c.element_mut(self.index) {
+            *slot = x;
+        }
+        c
+    }
+
+    fn modify_with<F, X>(&self, mut v: V, f: F) -> (V, Option<X>)
+        where F: FnOnce(V) -> (V, X)
+    {
+        let aux = v.element_mut(self.index).map(|slot| {
+            let (x, aux) = f(mem::take(slot));
+            *slot = x;
+            aux
+        });
+        (v, aux)
+    }
+}
+
+/// One step of a `Path`.
+#[derive(Clone,Debug,PartialEq,Eq,Hash)]
+pub enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+impl Segment {
+    /// The partial lens addressing this step, erased so that steps of either
+    /// kind can be composed at runtime.
+    pub fn lens<V: Tree>(&self) -> Box<dyn DynPartialLens<V, V>> {
+        match *self {
+            Segment::Key(ref k) => Box::new(Key::of(k.as_str())),
+            Segment::Index(i) => Box::new(Index::of(i)),
+        }
+    }
+
+    fn step<'a, V: Tree>(&self, v: &'a V) -> Option<&'a V> {
+        match *self {
+            Segment::Key(ref k) => v.member(k),
+            Segment::Index(i) => v.element(i),
+        }
+    }
+
+    fn step_mut<'a, V: Tree>(&self, v: &'a mut V) -> Option<&'a mut V> {
+        match *self {
+            Segment::Key(ref k) => v.member_mut(k),
+            Segment::Index(i) => v.element_mut(i),
+        }
+    }
+}
+
+impl Display for Segment {
+    fn fmt(&self, fm: &mut Formatter) -> fmt::Result {
+        match *self {
+            Segment::Key(ref k) => {
+                write!(fm, ".")?;
+                write_key(fm, k)
+            }
+            Segment::Index(i) => write!(fm, "[{}]", i),
+        }
+    }
+}
+
+/// A path into a `Tree`, such as `servers[2].ports.http`.  The empty path
+/// addresses the whole value.  A `.`, `[`, `]` or `\` within a key is escaped
+/// with a `\`, as in `version\.major`.
+#[derive(Clone,Debug,Default,PartialEq,Eq,Hash)]
+pub struct Path {
+    segments: Vec<Segment>,
+}
+
+impl Path {
+    #[inline]
+    pub fn of(segments: Vec<Segment>) -> Self {
+        Path { segments }
+    }
+
+    pub fn parse(s: &str) -> Result<Self, ParseError> {
+        let mut chars = s.chars().enumerate().peekable();
+        let mut segments = Vec::new();
+        // Columns are one-based, counted in characters.
+        let column = |i: usize| i + 1;
+        let mut first = true;
+        while let Some((i, c)) = chars.next() {
+            let key_start = match c {
+                '.' if !first => None,
+                '[' => {
+                    let mut digits = String::new();
+                    while let Some(&(_, d)) = chars.peek() {
+                        if !d.is_ascii_digit() {
+                            break;
+                        }
+                        digits.push(d);
+                        chars.next();
+                    }
+                    let index = match digits.parse() {
+                        Ok(n) => n,
+                        Err(_) => {
+                            let j = chars.peek().map_or(s.chars().count(), |&(j, _)| j);
+                            return Err(ParseError::of(column(j), ParseErrorKind::BadIndex));
+                        }
+                    };
+                    match chars.next() {
+                        Some((_, ']')) => {}
+                        Some((j, c)) => {
+                            return Err(ParseError::of(column(j), ParseErrorKind::Unexpected(c)))
+                        }
+                        None => {
+                            return Err(ParseError::of(column(s.chars().count()),
+                                                      ParseErrorKind::UnclosedBracket))
+                        }
+                    }
+                    segments.push(Segment::Index(index));
+                    first = false;
+                    continue;
+                }
+                ']' | '.' => return Err(ParseError::of(column(i), ParseErrorKind::Unexpected(c))),
+                c if first => Some((i, c)),
+                c => return Err(ParseError::of(column(i), ParseErrorKind::Unexpected(c))),
+            };
+            let mut key = String::new();
+            let mut pending = key_start;
+            loop {
+                let (j, c) = match pending.take() {
+                    Some(jc) => jc,
+                    None => match chars.peek() {
+                        Some(&(j, c)) if !ends_key(c) => {
+                            chars.next();
+                            (j, c)
+                        }
+                        _ => break,
+                    },
+                };
+                if c == '\\' {
+                    match chars.next() {
+                        Some((_, e)) => key.push(e),
+                        None => {
+                            return Err(ParseError::of(column(j), ParseErrorKind::DanglingEscape))
+                        }
+                    }
+                } else {
+                    key.push(c);
+                }
+            }
+            if key.is_empty() {
+                let j = chars.peek().map_or(s.chars().count(), |&(j, _)| j);
+                return Err(ParseError::of(column(j), ParseErrorKind::EmptyKey));
+            }
+            segments.push(Segment::Key(key));
+            first = false;
+        }
+        Ok(Path::of(segments))
+    }
+
+    #[inline]
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// Borrow the value this path addresses, or report the first segment
+    /// that could not be found.
+    pub fn get<'a, V: Tree>(&self, mut v: &'a V) -> Result<&'a V, LookupError> {
+        for (depth, seg) in self.segments.iter().enumerate() {
+            v = seg.step(v).ok_or_else(|| LookupError::of(depth, seg.clone()))?;
+        }
+        Ok(v)
+    }
+
+    /// Mutably borrow the value this path addresses, or report the first
+    /// segment that could not be found.
+    pub fn get_mut<'a, V: Tree>(&self, mut v: &'a mut V) -> Result<&'a mut V, LookupError> {
+        for (depth, seg) in self.segments.iter().enumerate() {
+            v = seg.step_mut(v).ok_or_else(|| LookupError::of(depth, seg.clone()))?;
+        }
+        Ok(v)
+    }
+
+    /// The partial lens addressing this path, composed from the lenses of its
+    /// segments.  Its failures can't say which segment was missing; `try_get`
+    /// and `try_modify` report that too.
+    pub fn lens<V: Tree>(&self) -> Box<dyn DynPartialLens<V, V>> {
+        self.segments
+            .iter()
+            .fold(Box::new(Identity::<V, V>::mk()) as Box<dyn DynPartialLens<V, V>>,
+                  |outer, seg| Box::new(Compose::of(seg.lens(), outer)))
+    }
+
+    /// Take the value this path addresses out of `v`, or report the first
+    /// segment that could not be found, giving `v` back unchanged.
+    pub fn try_get<V: Tree>(&self, v: V) -> Result<V, (LookupError, V)> {
+        self.take(v).map(|(x, _)| x)
+    }
+
+    /// Replace the value this path addresses with its image under `f`, or
+    /// report the first segment that could not be found, giving `v` back
+    /// unchanged.
+    pub fn try_modify<V: Tree, F>(&self, v: V, f: F) -> Result<V, (LookupError, V)>
+        where F: FnOnce(V) -> V
+    {
+        self.take(v).map(|(x, taken)| refill(taken, f(x)))
+    }
+
+    /// Walk down `v`, keeping what is left of each level so that it can be
+    /// rebuilt.
+    fn take<V: Tree>(&self, mut v: V) -> Result<Walked<V>, (LookupError, V)> {
+        let mut taken = Vec::with_capacity(self.segments.len());
+        for (depth, seg) in self.segments.iter().enumerate() {
+            let l = seg.lens();
+            match l.try_get_context(v) {
+                Ok((x, c)) => {
+                    taken.push((l, c));
+                    v = x;
+                }
+                Err(w) => return Err((LookupError::of(depth, seg.clone()), refill(taken, w))),
+            }
+        }
+        Ok((v, taken))
+    }
+}
+
+/// One level of a `Path` walked down by `Path::take`, with its lens.
+type Taken<V> = (Box<dyn DynPartialLens<V, V>>, Box<dyn Any>);
+
+/// The value a `Path` addresses, with the levels `Path::take` walked down to
+/// reach it.
+type Walked<V> = (V, Vec<Taken<V>>);
+
+/// Rebuild a value from the levels left by `Path::take`.
+fn refill<V>(taken: Vec<Taken<V>>, x: V) -> V {
+    taken.into_iter().rev().fold(x, |x, (l, c)| l.fill_context(c, x))
+}
+
+/// Whether `c`, unescaped, ends a key.
+#[inline]
+fn ends_key(c: char) -> bool {
+    c == '.' || c == '[' || c == ']'
+}
+
+/// Whether `c` must be escaped in a key.
+#[inline]
+fn is_special(c: char) -> bool {
+    ends_key(c) || c == '\\'
+}
+
+/// Write a key, escaping the characters that would otherwise end it.
+fn write_key(fm: &mut Formatter, k: &str) -> fmt::Result {
+    for c in k.chars() {
+        if is_special(c) {
+            write!(fm, "\\")?;
+        }
+        write!(fm, "{}", c)?;
+    }
+    Ok(())
+}
+
+impl FromStr for Path {
+    type Err = ParseError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        Path::parse(s)
+    }
+}
+
+impl Display for Path {
+    fn fmt(&self, fm: &mut Formatter) -> fmt::Result {
+        for (depth, seg) in self.segments.iter().enumerate() {
+            match *seg {
+                Segment::Key(ref k) if depth == 0 => write_key(fm, k)?,
+                ref seg => write!(fm, "{}", seg)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// What went wrong while parsing a `Path`.
+#[derive(Clone,Copy,Debug,PartialEq,Eq,Hash)]
+pub enum ParseErrorKind {
+    /// A `.` with no key after it.
+    EmptyKey,
+    /// A `[` not followed by a valid index.
+    BadIndex,
+    /// A `[` with no matching `]`.
+    UnclosedBracket,
+    /// A `\` with nothing after it to escape.
+    DanglingEscape,
+    Unexpected(char),
+}
+
+/// An error from parsing a `Path`, with the (one-based) column it occurred
+/// at.
+#[derive(Clone,Copy,Debug,PartialEq,Eq,Hash)]
+pub struct ParseError {
+    column: usize,
+    kind: ParseErrorKind,
+}
+
+impl ParseError {
+    #[inline]
+    fn of(column: usize, kind: ParseErrorKind) -> Self {
+        ParseError { column, kind }
+    }
+
+    #[inline]
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    #[inline]
+    pub fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, fm: &mut Formatter) -> fmt::Result {
+        match self.kind {
+            ParseErrorKind::EmptyKey => write!(fm, "expected a key")?,
+            ParseErrorKind::BadIndex => write!(fm, "expected an index")?,
+            ParseErrorKind::UnclosedBracket => write!(fm, "expected `]`")?,
+            ParseErrorKind::DanglingEscape => write!(fm, "expected a character after `\\`")?,
+            ParseErrorKind::Unexpected(c) => write!(fm, "unexpected `{}`", c)?,
+        }
+        write!(fm, " at column {}", self.column)
+    }
+}
+
+impl Error for ParseError {}
+
+/// An error from looking up a `Path`, naming the first segment that was
+/// missing.
+#[derive(Clone,Debug,PartialEq,Eq,Hash)]
+pub struct LookupError {
+    depth: usize,
+    segment: Segment,
+}
+
+impl LookupError {
+    #[inline]
+    fn of(depth: usize, segment: Segment) -> Self {
+        LookupError { depth, segment }
+    }
+
+    /// How many segments were found before the missing one.
+    #[inline]
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    #[inline]
+    pub fn segment(&self) -> &Segment {
+        &self.segment
+    }
+}
+
+impl Display for LookupError {
+    fn fmt(&self, fm: &mut Formatter) -> fmt::Result {
+        write!(fm, "missing path segment `{}` at depth {}", self.segment, self.depth)
+    }
+}
+
+impl Error for LookupError {}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+    use super::*;
+    use ::PartialLens;
+
+    fn config() -> Value {
+        let mut ports = BTreeMap::new();
+        ports.insert("http".to_owned(), Value::from(80.0));
+        let mut server = BTreeMap::new();
+        server.insert("ports".to_owned(), Value::from(ports));
+        let mut root = BTreeMap::new();
+        root.insert("servers".to_owned(),
+                    Value::from(vec![Value::Null, Value::Null, Value::from(server)]));
+        Value::from(root)
+    }
+
+    #[test]
+    fn test_parse() {
+        let p: Path = "servers[2].ports.http".parse().unwrap();
+        assert_eq!(p.segments(),
+                   &[Segment::Key("servers".to_owned()),
+                     Segment::Index(2),
+                     Segment::Key("ports".to_owned()),
+                     Segment::Key("http".to_owned())]);
+        assert_eq!(p.to_string(), "servers[2].ports.http");
+        assert_eq!(Path::parse(""), Ok(Path::default()));
+        assert_eq!(Path::parse("[0][1]").unwrap().segments(),
+                   &[Segment::Index(0), Segment::Index(1)]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = |s: &str| {
+            let e = Path::parse(s).unwrap_err();
+            (e.column(), e.kind())
+        };
+        assert_eq!(err("servers..http"), (9, ParseErrorKind::EmptyKey));
+        assert_eq!(err("servers."), (9, ParseErrorKind::EmptyKey));
+        assert_eq!(err("servers[x]"), (9, ParseErrorKind::BadIndex));
+        assert_eq!(err("servers[2"), (10, ParseErrorKind::UnclosedBracket));
+        assert_eq!(err("servers]"), (8, ParseErrorKind::Unexpected(']')));
+        assert_eq!(err(".servers"), (1, ParseErrorKind::Unexpected('.')));
+        assert_eq!(Path::parse("a[").unwrap_err().to_string(), "expected an index at column 3");
+        assert_eq!(err("a.b\\"), (4, ParseErrorKind::DanglingEscape));
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        let p = Path::of(vec![Segment::Key("a.b".to_owned()),
+                              Segment::Index(3),
+                              Segment::Key("[c]".to_owned()),
+                              Segment::Key("d\\e".to_owned()),
+                              Segment::Key("f".to_owned())]);
+        assert_eq!(p.to_string(), "a\\.b[3].\\[c\\].d\\\\e.f");
+        assert_eq!(Path::parse(&p.to_string()), Ok(p));
+        for s in &["servers[2].ports.http", "[0].x", "\\[y", "k\\.\\.z"] {
+            assert_eq!(Path::parse(s).unwrap().to_string(), *s);
+        }
+    }
+
+    #[test]
+    fn test_lookup() {
+        let v = config();
+        let p = Path::parse("servers[2].ports.http").unwrap();
+        assert_eq!(p.get(&v), Ok(&Value::Number(80.0)));
+        let e = Path::parse("servers[2].ports.https").unwrap().get(&v).unwrap_err();
+        assert_eq!(e.segment(), &Segment::Key("https".to_owned()));
+        assert_eq!(e.depth(), 3);
+        let e = Path::parse("servers[5].ports").unwrap().get(&v).unwrap_err();
+        assert_eq!(e.to_string(), "missing path segment `[5]` at depth 1");
+    }
+
+    #[test]
+    fn test_path_lens() {
+        let p = Path::parse("servers[2].ports.http").unwrap();
+        let l = p.lens();
+        let v = l.modify(config(), |_| Value::from(8080.0));
+        assert_eq!(p.get(&v), Ok(&Value::Number(8080.0)));
+        assert_eq!(l.try_get(v), Ok(Value::Number(8080.0)));
+        assert_eq!(Path::parse("servers[3]").unwrap().lens().try_get(config()), Err(config()));
+        let (x, c) = l.try_get_context(config()).ok().unwrap();
+        assert_eq!(x, Value::Number(80.0));
+        assert_eq!(l.fill_context(c, Value::from(true)),
+                   l.set(config(), Value::Bool(true)));
+    }
+
+    #[test]
+    fn test_path_try_get() {
+        let p = Path::parse("servers[2].ports.http").unwrap();
+        assert_eq!(p.try_get(config()), Ok(Value::Number(80.0)));
+        let v = p.try_modify(config(), |_| Value::from(8080.0)).unwrap();
+        assert_eq!(p.get(&v), Ok(&Value::Number(8080.0)));
+        let q = Path::parse("servers[2].hosts[0]").unwrap();
+        let (e, w) = q.try_get(config()).unwrap_err();
+        assert_eq!((e.depth(), e.segment()), (2, &Segment::Key("hosts".to_owned())));
+        assert_eq!(w, config());
+        let (e, w) = q.try_modify(config(), |_| Value::Null).unwrap_err();
+        assert_eq!(e.to_string(), "missing path segment `.hosts` at depth 2");
+        assert_eq!(w, config());
+    }
+}
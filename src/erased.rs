@@ -0,0 +1,328 @@
+//! Object-safe companions to `PartialLens`, `Lens` and `Prism`, so that
+//! lenticuloids of different concrete types can be stored side by side and
+//! chosen at runtime.  `Box` and `Arc` pointers to these trait objects are
+//! themselves full lenticuloids, and compose like any other.
+//!
+//! The context of an erased lenticuloid is boxed as `Box<dyn Any>`.  Filling
+//! it with a context taken by a different lenticuloid is reported as a
+//! `ContextMismatch` by `dyn_fill_context`; through `PartialLens`, whose
+//! contract rules it out, it panics.
+//!
+//! The pointers are lenticuloids whether or not their trait objects are also
+//! `Send + Sync`, so erased lenticuloids can be shared between threads.
+
+use std::any::Any;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::sync::Arc;
+use super::{Injector, Lens, Lenticuloid, PartialLens, Prism};
+
+/// The object-safe companion to `PartialLens`.
+pub trait DynPartialLens<S, A, T = S, B = A> {
+    fn dyn_try_get(&self, v: S) -> Result<A, T>;
+
+    fn dyn_try_get_context(&self, v: S) -> Result<(A, Box<dyn Any>), T>;
+
+    /// Rebuild a source from a context left by `dyn_try_get_context`, unless
+    /// the context was left by a lenticuloid with a different context type.
+    fn dyn_fill_context(&self, c: Box<dyn Any>, x: B) -> Result<T, ContextMismatch>;
+
+    fn dyn_try_get_inject(&self, v: S) -> Result<(A, Injector<'_, B, T>), T>;
+
+    fn dyn_set(&self, v: S, x: B) -> T;
+
+    fn dyn_modify(&self, v: S, f: &mut dyn FnMut(A) -> B) -> T;
+
+    fn dyn_at_initial(&self) -> Box<dyn DynPartialLens<S, A>>;
+
+    fn dyn_at_final(&self) -> Box<dyn DynPartialLens<T, B>>;
+}
+
+impl<L: PartialLens>
+    DynPartialLens<L::InitialSource, L::InitialTarget, L::FinalSource, L::FinalTarget> for L
+    where L::AtInitial: PartialLens + 'static,
+          L::AtFinal: PartialLens + 'static,
+          L::Context: 'static,
+          <L::AtInitial as PartialLens>::Context: 'static,
+          <L::AtFinal as PartialLens>::Context: 'static
+{
+    #[inline]
+    fn dyn_try_get(&self, v: L::InitialSource) -> Result<L::InitialTarget, L::FinalSource> {
+        self.try_get(v)
+    }
+
+    #[inline]
+    fn dyn_try_get_context(&self,
+                           v: L::InitialSource)
+                           -> Result<(L::InitialTarget, Box<dyn Any>), L::FinalSource> {
+        self.try_get_context(v).map(|(x, c)| (x, Box::new(c) as Box<dyn Any>))
+    }
+
+    #[inline]
+    fn dyn_fill_context(&self,
+                        c: Box<dyn Any>,
+                        x: L::FinalTarget)
+                        -> Result<L::FinalSource, ContextMismatch> {
+        c.downcast::<L::Context>().map(|c| self.fill_context(*c, x)).map_err(|_| ContextMismatch)
+    }
+
+    #[inline]
+    fn dyn_try_get_inject(&self,
+                          v: L::InitialSource)
+                          -> Result<(L::InitialTarget, Injector<'_, L::FinalTarget, L::FinalSource>),
+                                    L::FinalSource> {
+        self.try_get_inject(v)
+    }
+
+    #[inline]
+    fn dyn_set(&self, v: L::InitialSource, x: L::FinalTarget) -> L::FinalSource {
+        self.set(v, x)
+    }
+
+    #[inline]
+    fn dyn_modify(&self,
+                  v: L::InitialSource,
+                  f: &mut dyn FnMut(L::InitialTarget) -> L::FinalTarget)
+                  -> L::FinalSource {
+        self.modify(v, f)
+    }
+
+    fn dyn_at_initial(&self) -> Box<dyn DynPartialLens<L::InitialSource, L::InitialTarget>> {
+        Box::new(self.at_initial())
+    }
+
+    fn dyn_at_final(&self) -> Box<dyn DynPartialLens<L::FinalSource, L::FinalTarget>> {
+        Box::new(self.at_final())
+    }
+}
+
+/// The object-safe companion to `Lens`.
+pub trait DynLens<S, A, T = S, B = A>: DynPartialLens<S, A, T, B> {
+    fn dyn_get(&self, v: S) -> A;
+
+    fn dyn_at_initial_lens(&self) -> Box<dyn DynLens<S, A>>;
+
+    fn dyn_at_final_lens(&self) -> Box<dyn DynLens<T, B>>;
+}
+
+impl<L: Lens> DynLens<L::InitialSource, L::InitialTarget, L::FinalSource, L::FinalTarget> for L
+    where L::AtInitial: Lens + 'static,
+          L::AtFinal: Lens + 'static,
+          L::Context: 'static,
+          <L::AtInitial as PartialLens>::Context: 'static,
+          <L::AtFinal as PartialLens>::Context: 'static
+{
+    #[inline]
+    fn dyn_get(&self, v: L::InitialSource) -> L::InitialTarget {
+        self.get(v)
+    }
+
+    fn dyn_at_initial_lens(&self) -> Box<dyn DynLens<L::InitialSource, L::InitialTarget>> {
+        Box::new(self.at_initial())
+    }
+
+    fn dyn_at_final_lens(&self) -> Box<dyn DynLens<L::FinalSource, L::FinalTarget>> {
+        Box::new(self.at_final())
+    }
+}
+
+/// The object-safe companion to `Prism`.
+pub trait DynPrism<S, A, T = S, B = A>: DynPartialLens<S, A, T, B> {
+    fn dyn_inject(&self, v: B) -> T;
+
+    fn dyn_at_initial_prism(&self) -> Box<dyn DynPrism<S, A>>;
+
+    fn dyn_at_final_prism(&self) -> Box<dyn DynPrism<T, B>>;
+}
+
+impl<L: Prism> DynPrism<L::InitialSource, L::InitialTarget, L::FinalSource, L::FinalTarget> for L
+    where L::AtInitial: Prism + 'static,
+          L::AtFinal: Prism + 'static,
+          L::Context: 'static,
+          <L::AtInitial as PartialLens>::Context: 'static,
+          <L::AtFinal as PartialLens>::Context: 'static
+{
+    #[inline]
+    fn dyn_inject(&self, v: L::FinalTarget) -> L::FinalSource {
+        self.inject(v)
+    }
+
+    fn dyn_at_initial_prism(&self) -> Box<dyn DynPrism<L::InitialSource, L::InitialTarget>> {
+        Box::new(self.at_initial())
+    }
+
+    fn dyn_at_final_prism(&self) -> Box<dyn DynPrism<L::FinalSource, L::FinalTarget>> {
+        Box::new(self.at_final())
+    }
+}
+
+/// The error from filling an erased lenticuloid with a context taken by a
+/// different one.
+#[derive(Clone,Copy,Debug,PartialEq,Eq,Hash)]
+pub struct ContextMismatch;
+
+impl Display for ContextMismatch {
+    fn fmt(&self, fm: &mut Formatter) -> fmt::Result {
+        fm.write_str("context taken by a different lenticuloid")
+    }
+}
+
+impl Error for ContextMismatch {}
+
+macro_rules! erased_partial_lens {
+    ($ptr:ident, $tr:ident, $at_initial:ident, $at_final:ident $(, $bound:ident)*) => {
+        impl<S, A, T, B> Lenticuloid for $ptr<dyn $tr<S, A, T, B> $(+ $bound)*> {
+            type InitialSource = S;
+
+            type InitialTarget = A;
+
+            type FinalSource = T;
+
+            type FinalTarget = B;
+
+            type AtInitial = $ptr<dyn $tr<S, A>>;
+
+            fn at_initial(&self) -> Self::AtInitial {
+                $ptr::from((**self).$at_initial())
+            }
+
+            type AtFinal = $ptr<dyn $tr<T, B>>;
+
+            fn at_final(&self) -> Self::AtFinal {
+                $ptr::from((**self).$at_final())
+            }
+        }
+
+        impl_traversal_via_partial_lens!([S, A, T, B] $ptr<dyn $tr<S, A, T, B> $(+ $bound)*>);
+
+        impl<S, A, T, B> PartialLens for $ptr<dyn $tr<S, A, T, B> $(+ $bound)*> {
+            type Context = Box<dyn Any>;
+
+            #[inline]
+            fn try_get(&self, v: S) -> Result<A, T> {
+                (**self).dyn_try_get(v)
+            }
+
+            #[inline]
+            fn try_get_context(&self, v: S) -> Result<(A, Box<dyn Any>), T> {
+                (**self).dyn_try_get_context(v)
+            }
+
+            /// # Panics
+            ///
+            /// If `c` was not taken by this lenticuloid, contrary to the
+            /// contract of `PartialLens`.
+            #[inline]
+            fn fill_context(&self, c: Box<dyn Any>, x: B) -> T {
+                match (**self).dyn_fill_context(c, x) {
+                    Ok(t) => t,
+                    Err(e) => panic!("{}", e),
+                }
+            }
+
+            #[inline]
+            fn try_get_inject(&self, v: S) -> Result<(A, Injector<'_, B, T>), T> {
+                (**self).dyn_try_get_inject(v)
+            }
+
+            #[inline]
+            fn set(&self, v: S, x: B) -> T {
+                (**self).dyn_set(v, x)
+            }
+
+            #[inline]
+            fn modify<F: FnOnce(A) -> B>(&self, v: S, f: F) -> T {
+                let mut f_opt = Some(f);
+                (**self).dyn_modify(v, &mut |x| (f_opt.take().unwrap())(x))
+            }
+
+            #[inline]
+            fn modify_with<F, X>(&self, v: S, f: F) -> (T, Option<X>)
+                where F: FnOnce(A) -> (B, X)
+            {
+                let mut f_opt = Some(f);
+                let mut aux = None;
+                let t = (**self).dyn_modify(v, &mut |x| {
+                    let (y, a) = (f_opt.take().unwrap())(x);
+                    aux = Some(a);
+                    y
+                });
+                (t, aux)
+            }
+        }
+    };
+}
+
+macro_rules! erased_lenticuloids {
+    ($ptr:ident $(, $bound:ident)*) => {
+        erased_partial_lens!($ptr, DynPartialLens, dyn_at_initial, dyn_at_final $(, $bound)*);
+
+        erased_partial_lens!($ptr, DynLens, dyn_at_initial_lens, dyn_at_final_lens $(, $bound)*);
+
+        impl_getter_via_lens!([S, A, T, B] $ptr<dyn DynLens<S, A, T, B> $(+ $bound)*>);
+
+        impl<S, A, T, B> Lens for $ptr<dyn DynLens<S, A, T, B> $(+ $bound)*> {
+            #[inline]
+            fn get(&self, v: S) -> A {
+                (**self).dyn_get(v)
+            }
+        }
+
+        erased_partial_lens!($ptr, DynPrism, dyn_at_initial_prism, dyn_at_final_prism $(, $bound)*);
+
+        impl_review_via_prism!([S, A, T, B] $ptr<dyn DynPrism<S, A, T, B> $(+ $bound)*>);
+
+        impl<S, A, T, B> Prism for $ptr<dyn DynPrism<S, A, T, B> $(+ $bound)*> {
+            #[inline]
+            fn inject(&self, v: B) -> T {
+                (**self).dyn_inject(v)
+            }
+        }
+    };
+}
+
+erased_lenticuloids!(Box);
+
+erased_lenticuloids!(Box, Send, Sync);
+
+erased_lenticuloids!(Arc);
+
+erased_lenticuloids!(Arc, Send, Sync);
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use std::thread;
+    use super::*;
+    use ::{ComposeExt, Lens, PartialLens, Prism};
+    use errors::Some_;
+
+    type Pair = (u8, u8);
+
+    #[test]
+    fn test_registry() {
+        let mut registry: HashMap<&str, Box<dyn DynLens<Pair, u8>>> = HashMap::new();
+        registry.insert("first", Box::new(field_lens!((u8, u8) => 0: u8)));
+        registry.insert("second", Box::new(field_lens!((u8, u8) => 1: u8)));
+        let l = &registry["second"];
+        assert_eq!(l.get((1, 2)), 2);
+        assert_eq!(l.modify((1, 2), |x| x + 1), (1, 3));
+        let (x, c) = registry["first"].try_get_context((3, 4)).ok().unwrap();
+        assert_eq!(registry["first"].fill_context(c, x * 2), (6, 4));
+        let (_, c) = (*registry["first"]).dyn_try_get_context((5, 6)).ok().unwrap();
+        assert_eq!((*registry["second"]).dyn_fill_context(c, 7), Err(ContextMismatch));
+    }
+
+    #[test]
+    fn test_erased_compose() {
+        let p: Arc<dyn DynPrism<Option<Pair>, Pair> + Send + Sync> = Arc::new(Some_::mk());
+        let l = field_lens!((u8, u8) => 1: u8).compose(p.clone());
+        assert_eq!(l.modify_with(Some((1, 2)), |x| (x * 5, x)), (Some((1, 10)), Some(2)));
+        assert_eq!(l.try_get(None), Err(None));
+        assert_eq!(p.at_final().inject((7, 8)), Some((7, 8)));
+        let q = p.clone();
+        let t = thread::spawn(move || q.modify(Some((1, 2)), |(x, y)| (y, x)));
+        assert_eq!(t.join().unwrap(), Some((2, 1)));
+    }
+}
@@ -1,2 +1,1352 @@
 //! These lenticuloids deal with Rust's basic error-handling types: `Option`
 //! and `Result`.
+
+use std::fmt::{self, Debug, Formatter};
+use std::marker::PhantomData;
+use super::{Iso, Lens, Lenticuloid, PartialLens, Prism};
+
+/// A `Prism` family focusing on the payload of `Some`.
+pub struct Some_<A, B = A> {
+    phantom_aa: PhantomData<fn(Option<A>) -> A>,
+    phantom_bb: PhantomData<fn(B) -> Option<B>>,
+}
+
+impl<A, B> Some_<A, B> {
+    #[cfg(not(feature = "nightly"))]
+    #[inline]
+    pub fn mk() -> Self {
+        Some_ { phantom_aa: PhantomData,
+                phantom_bb: PhantomData, }
+    }
+
+    #[cfg(feature = "nightly")]
+    #[inline]
+    pub const fn mk() -> Self {
+        Some_ { phantom_aa: PhantomData,
+                phantom_bb: PhantomData, }
+    }
+}
+
+impl<A, B> Debug for Some_<A, B> {
+    fn fmt(&self, fm: &mut Formatter) -> fmt::Result {
+        fm.debug_struct("Some_")
+          .field("phantom_aa", &self.phantom_aa)
+          .field("phantom_bb", &self.phantom_bb)
+          .finish()
+    }
+}
+
+impl<A, B> Clone for Some_<A, B> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<A, B> Copy for Some_<A, B> {}
+
+impl<A, B> Default for Some_<A, B> {
+    #[inline]
+    fn default() -> Self {
+        Self::mk()
+    }
+}
+
+impl<A, B> Lenticuloid for Some_<A, B> {
+    type InitialSource = Option<A>;
+
+    type InitialTarget = A;
+
+    type FinalSource = Option<B>;
+
+    type FinalTarget = B;
+
+    type AtInitial = Some_<A, A>;
+
+    fn at_initial(&self) -> Self::AtInitial {
+        Some_::mk()
+    }
+
+    type AtFinal = Some_<B, B>;
+
+    fn at_final(&self) -> Self::AtFinal {
+        Some_::mk()
+    }
+}
+
+impl_traversal_via_partial_lens!([A, B] Some_<A, B>);
+
+impl<A, B> PartialLens for Some_<A, B> {
+    type Context = ();
+
+    #[inline]
+    fn try_get(&self, v: Option<A>) -> Result<A, Option<B>> {
+        v.ok_or(None)
+    }
+
+    #[inline]
+    fn try_get_context(&self, v: Option<A>) -> Result<(A, ()), Option<B>> {
+        match v {
+            Some(x) => Ok((x, ())),
+            None => Err(None),
+        }
+    }
+
+    #[inline]
+    fn fill_context(&self, _c: (), x: B) -> Option<B> {
+        Some(x)
+    }
+
+    #[inline]
+    fn set(&self, v: Option<A>, x: B) -> Option<B> {
+        v.map(move |_| x)
+    }
+
+    #[inline]
+    fn exchange(&self, v: Option<A>, x: B) -> (Option<A>, Option<B>) {
+        match v {
+            Some(y) => (Some(y), Some(x)),
+            None => (None, None),
+        }
+    }
+
+    #[inline]
+    fn modify<F: FnOnce(A) -> B>(&self, v: Option<A>, f: F) -> Option<B> {
+        v.map(f)
+    }
+
+    #[inline]
+    fn modify_with<F, X>(&self, v: Option<A>, f: F) -> (Option<B>, Option<X>)
+        where F: FnOnce(A) -> (B, X)
+    {
+        match v {
+            Some(x) => {
+                let (a, b) = f(x);
+                (Some(a), Some(b))
+            }
+            None => (None, None),
+        }
+    }
+}
+
+impl_review_via_prism!([A, B] Some_<A, B>);
+
+impl<A, B> Prism for Some_<A, B> {
+    #[inline]
+    fn inject(&self, v: B) -> Option<B> {
+        Some(v)
+    }
+}
+
+/// A `Prism` focusing on the absence of a value in an `Option`.
+pub struct None_<A> {
+    phantom_au: PhantomData<fn(Option<A>)>,
+    phantom_ua: PhantomData<fn(()) -> Option<A>>,
+}
+
+impl<A> None_<A> {
+    #[cfg(not(feature = "nightly"))]
+    #[inline]
+    pub fn mk() -> Self {
+        None_ { phantom_au: PhantomData,
+                phantom_ua: PhantomData, }
+    }
+
+    #[cfg(feature = "nightly")]
+    #[inline]
+    pub const fn mk() -> Self {
+        None_ { phantom_au: PhantomData,
+                phantom_ua: PhantomData, }
+    }
+}
+
+impl<A> Debug for None_<A> {
+    fn fmt(&self, fm: &mut Formatter) -> fmt::Result {
+        fm.debug_struct("None_")
+          .field("phantom_au", &self.phantom_au)
+          .field("phantom_ua", &self.phantom_ua)
+          .finish()
+    }
+}
+
+impl<A> Clone for None_<A> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<A> Copy for None_<A> {}
+
+impl<A> Default for None_<A> {
+    #[inline]
+    fn default() -> Self {
+        Self::mk()
+    }
+}
+
+impl<A> Lenticuloid for None_<A> {
+    type InitialSource = Option<A>;
+
+    type InitialTarget = ();
+
+    type FinalSource = Option<A>;
+
+    type FinalTarget = ();
+
+    type AtInitial = None_<A>;
+
+    fn at_initial(&self) -> Self::AtInitial {
+        None_::mk()
+    }
+
+    type AtFinal = None_<A>;
+
+    fn at_final(&self) -> Self::AtFinal {
+        None_::mk()
+    }
+}
+
+impl_traversal_via_partial_lens!([A] None_<A>);
+
+impl<A> PartialLens for None_<A> {
+    type Context = ();
+
+    #[inline]
+    fn try_get(&self, v: Option<A>) -> Result<(), Option<A>> {
+        match v {
+            None => Ok(()),
+            s => Err(s),
+        }
+    }
+
+    #[inline]
+    fn try_get_context(&self, v: Option<A>) -> Result<((), ()), Option<A>> {
+        match v {
+            None => Ok(((), ())),
+            s => Err(s),
+        }
+    }
+
+    #[inline]
+    fn fill_context(&self, _c: (), _x: ()) -> Option<A> {
+        None
+    }
+
+    #[inline]
+    fn set(&self, v: Option<A>, _x: ()) -> Option<A> {
+        v
+    }
+
+    #[inline]
+    fn exchange(&self, v: Option<A>, _x: ()) -> (Option<()>, Option<A>) {
+        match v {
+            None => (Some(()), None),
+            s => (None, s),
+        }
+    }
+
+    #[inline]
+    fn modify<F: FnOnce(())>(&self, v: Option<A>, f: F) -> Option<A> {
+        match v {
+            None => {
+                f(());
+                None
+            }
+            s => s,
+        }
+    }
+
+    #[inline]
+    fn modify_with<F, X>(&self, v: Option<A>, f: F) -> (Option<A>, Option<X>)
+        where F: FnOnce(()) -> ((), X)
+    {
+        match v {
+            None => (None, Some(f(()).1)),
+            s => (s, None),
+        }
+    }
+}
+
+impl_review_via_prism!([A] None_<A>);
+
+impl<A> Prism for None_<A> {
+    #[inline]
+    fn inject(&self, _v: ()) -> Option<A> {
+        None
+    }
+}
+
+/// A `Prism` family focusing on the payload of `Ok`.
+pub struct Ok_<A, B, E> {
+    phantom_aa: PhantomData<fn(Result<A, E>) -> A>,
+    phantom_bb: PhantomData<fn(B) -> Result<B, E>>,
+}
+
+impl<A, B, E> Ok_<A, B, E> {
+    #[cfg(not(feature = "nightly"))]
+    #[inline]
+    pub fn mk() -> Self {
+        Ok_ { phantom_aa: PhantomData,
+              phantom_bb: PhantomData, }
+    }
+
+    #[cfg(feature = "nightly")]
+    #[inline]
+    pub const fn mk() -> Self {
+        Ok_ { phantom_aa: PhantomData,
+              phantom_bb: PhantomData, }
+    }
+}
+
+impl<A, B, E> Debug for Ok_<A, B, E> {
+    fn fmt(&self, fm: &mut Formatter) -> fmt::Result {
+        fm.debug_struct("Ok_")
+          .field("phantom_aa", &self.phantom_aa)
+          .field("phantom_bb", &self.phantom_bb)
+          .finish()
+    }
+}
+
+impl<A, B, E> Clone for Ok_<A, B, E> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<A, B, E> Copy for Ok_<A, B, E> {}
+
+impl<A, B, E> Default for Ok_<A, B, E> {
+    #[inline]
+    fn default() -> Self {
+        Self::mk()
+    }
+}
+
+impl<A, B, E> Lenticuloid for Ok_<A, B, E> {
+    type InitialSource = Result<A, E>;
+
+    type InitialTarget = A;
+
+    type FinalSource = Result<B, E>;
+
+    type FinalTarget = B;
+
+    type AtInitial = Ok_<A, A, E>;
+
+    fn at_initial(&self) -> Self::AtInitial {
+        Ok_::mk()
+    }
+
+    type AtFinal = Ok_<B, B, E>;
+
+    fn at_final(&self) -> Self::AtFinal {
+        Ok_::mk()
+    }
+}
+
+impl_traversal_via_partial_lens!([A, B, E] Ok_<A, B, E>);
+
+impl<A, B, E> PartialLens for Ok_<A, B, E> {
+    type Context = ();
+
+    #[inline]
+    fn try_get(&self, v: Result<A, E>) -> Result<A, Result<B, E>> {
+        v.map_err(Err)
+    }
+
+    #[inline]
+    fn try_get_context(&self, v: Result<A, E>) -> Result<(A, ()), Result<B, E>> {
+        match v {
+            Ok(x) => Ok((x, ())),
+            Err(e) => Err(Err(e)),
+        }
+    }
+
+    #[inline]
+    fn fill_context(&self, _c: (), x: B) -> Result<B, E> {
+        Ok(x)
+    }
+
+    #[inline]
+    fn set(&self, v: Result<A, E>, x: B) -> Result<B, E> {
+        v.map(move |_| x)
+    }
+
+    #[inline]
+    fn exchange(&self, v: Result<A, E>, x: B) -> (Option<A>, Result<B, E>) {
+        match v {
+            Ok(y) => (Some(y), Ok(x)),
+            Err(e) => (None, Err(e)),
+        }
+    }
+
+    #[inline]
+    fn modify<F: FnOnce(A) -> B>(&self, v: Result<A, E>, f: F) -> Result<B, E> {
+        v.map(f)
+    }
+
+    #[inline]
+    fn modify_with<F, X>(&self, v: Result<A, E>, f: F) -> (Result<B, E>, Option<X>)
+        where F: FnOnce(A) -> (B, X)
+    {
+        match v {
+            Ok(x) => {
+                let (a, b) = f(x);
+                (Ok(a), Some(b))
+            }
+            Err(e) => (Err(e), None),
+        }
+    }
+}
+
+impl_review_via_prism!([A, B, E] Ok_<A, B, E>);
+
+impl<A, B, E> Prism for Ok_<A, B, E> {
+    #[inline]
+    fn inject(&self, v: B) -> Result<B, E> {
+        Ok(v)
+    }
+}
+
+/// A `Prism` family focusing on the payload of `Err`.
+pub struct Err_<T, E, F = E> {
+    phantom_ee: PhantomData<fn(Result<T, E>) -> E>,
+    phantom_ff: PhantomData<fn(F) -> Result<T, F>>,
+}
+
+impl<T, E, F> Err_<T, E, F> {
+    #[cfg(not(feature = "nightly"))]
+    #[inline]
+    pub fn mk() -> Self {
+        Err_ { phantom_ee: PhantomData,
+               phantom_ff: PhantomData, }
+    }
+
+    #[cfg(feature = "nightly")]
+    #[inline]
+    pub const fn mk() -> Self {
+        Err_ { phantom_ee: PhantomData,
+               phantom_ff: PhantomData, }
+    }
+}
+
+impl<T, E, F> Debug for Err_<T, E, F> {
+    fn fmt(&self, fm: &mut Formatter) -> fmt::Result {
+        fm.debug_struct("Err_")
+          .field("phantom_ee", &self.phantom_ee)
+          .field("phantom_ff", &self.phantom_ff)
+          .finish()
+    }
+}
+
+impl<T, E, F> Clone for Err_<T, E, F> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, E, F> Copy for Err_<T, E, F> {}
+
+impl<T, E, F> Default for Err_<T, E, F> {
+    #[inline]
+    fn default() -> Self {
+        Self::mk()
+    }
+}
+
+impl<T, E, F> Lenticuloid for Err_<T, E, F> {
+    type InitialSource = Result<T, E>;
+
+    type InitialTarget = E;
+
+    type FinalSource = Result<T, F>;
+
+    type FinalTarget = F;
+
+    type AtInitial = Err_<T, E, E>;
+
+    fn at_initial(&self) -> Self::AtInitial {
+        Err_::mk()
+    }
+
+    type AtFinal = Err_<T, F, F>;
+
+    fn at_final(&self) -> Self::AtFinal {
+        Err_::mk()
+    }
+}
+
+impl_traversal_via_partial_lens!([T, E, F] Err_<T, E, F>);
+
+impl<T, E, F> PartialLens for Err_<T, E, F> {
+    type Context = ();
+
+    #[inline]
+    fn try_get(&self, v: Result<T, E>) -> Result<E, Result<T, F>> {
+        match v {
+            Ok(x) => Err(Ok(x)),
+            Err(e) => Ok(e),
+        }
+    }
+
+    #[inline]
+    fn try_get_context(&self, v: Result<T, E>) -> Result<(E, ()), Result<T, F>> {
+        match v {
+            Ok(x) => Err(Ok(x)),
+            Err(e) => Ok((e, ())),
+        }
+    }
+
+    #[inline]
+    fn fill_context(&self, _c: (), x: F) -> Result<T, F> {
+        Err(x)
+    }
+
+    #[inline]
+    fn set(&self, v: Result<T, E>, x: F) -> Result<T, F> {
+        v.map_err(move |_| x)
+    }
+
+    #[inline]
+    fn exchange(&self, v: Result<T, E>, x: F) -> (Option<E>, Result<T, F>) {
+        match v {
+            Ok(y) => (None, Ok(y)),
+            Err(e) => (Some(e), Err(x)),
+        }
+    }
+
+    #[inline]
+    fn modify<G: FnOnce(E) -> F>(&self, v: Result<T, E>, f: G) -> Result<T, F> {
+        v.map_err(f)
+    }
+
+    #[inline]
+    fn modify_with<G, X>(&self, v: Result<T, E>, f: G) -> (Result<T, F>, Option<X>)
+        where G: FnOnce(E) -> (F, X)
+    {
+        match v {
+            Ok(y) => (Ok(y), None),
+            Err(e) => {
+                let (a, b) = f(e);
+                (Err(a), Some(b))
+            }
+        }
+    }
+}
+
+impl_review_via_prism!([T, E, F] Err_<T, E, F>);
+
+impl<T, E, F> Prism for Err_<T, E, F> {
+    #[inline]
+    fn inject(&self, v: F) -> Result<T, F> {
+        Err(v)
+    }
+}
+
+/// The shapes of the reshaping isomorphisms in this module, for use in
+/// phantom data.
+type SwapResultFn<T, E> = fn(Result<T, E>) -> Result<E, T>;
+type OptionAsResultFn<A> = fn(Option<A>) -> Result<A, ()>;
+type ResultAsOptionFn<A> = fn(Result<A, ()>) -> Option<A>;
+type FlattenOptionFn<A> = fn(Option<Option<A>>) -> Result<A, Option<()>>;
+type UnflattenOptionFn<A> = fn(Result<A, Option<()>>) -> Option<Option<A>>;
+type FlattenResultFn<T, E1, E2> = fn(NestedResult<T, E1, E2>) -> LayeredResult<T, E1, E2>;
+type UnflattenResultFn<T, E1, E2> = fn(LayeredResult<T, E1, E2>) -> NestedResult<T, E1, E2>;
+
+#[inline]
+fn swap_result<T, E>(v: Result<T, E>) -> Result<E, T> {
+    match v {
+        Ok(x) => Err(x),
+        Err(e) => Ok(e),
+    }
+}
+
+#[inline]
+fn option_to_result<A>(v: Option<A>) -> Result<A, ()> {
+    v.ok_or(())
+}
+
+#[inline]
+fn result_to_option<A>(v: Result<A, ()>) -> Option<A> {
+    v.ok()
+}
+
+#[inline]
+fn flatten_option<A>(v: Option<Option<A>>) -> Result<A, Option<()>> {
+    match v {
+        Some(Some(x)) => Ok(x),
+        Some(None) => Err(Some(())),
+        None => Err(None),
+    }
+}
+
+#[inline]
+fn unflatten_option<A>(v: Result<A, Option<()>>) -> Option<Option<A>> {
+    match v {
+        Ok(x) => Some(Some(x)),
+        Err(Some(())) => Some(None),
+        Err(None) => None,
+    }
+}
+
+/// A `Result` nested inside another.
+type NestedResult<T, E1, E2> = Result<Result<T, E1>, E2>;
+
+/// A `Result` whose error records which of two nested layers failed.
+type LayeredResult<T, E1, E2> = Result<T, Result<E1, E2>>;
+
+#[inline]
+fn flatten_result<T, E1, E2>(v: NestedResult<T, E1, E2>) -> LayeredResult<T, E1, E2> {
+    match v {
+        Ok(Ok(x)) => Ok(x),
+        Ok(Err(e)) => Err(Ok(e)),
+        Err(e) => Err(Err(e)),
+    }
+}
+
+#[inline]
+fn unflatten_result<T, E1, E2>(v: LayeredResult<T, E1, E2>) -> NestedResult<T, E1, E2> {
+    match v {
+        Ok(x) => Ok(Ok(x)),
+        Err(Ok(e)) => Ok(Err(e)),
+        Err(Err(e)) => Err(e),
+    }
+}
+
+/// An `Iso` family exchanging the success and failure cases of a `Result`.
+pub struct SwapResult<T, E, U = T, F = E> {
+    phantom_te: PhantomData<SwapResultFn<T, E>>,
+    phantom_fu: PhantomData<SwapResultFn<F, U>>,
+}
+
+impl<T, E, U, F> SwapResult<T, E, U, F> {
+    #[cfg(not(feature = "nightly"))]
+    #[inline]
+    pub fn mk() -> Self {
+        SwapResult { phantom_te: PhantomData,
+                     phantom_fu: PhantomData, }
+    }
+
+    #[cfg(feature = "nightly")]
+    #[inline]
+    pub const fn mk() -> Self {
+        SwapResult { phantom_te: PhantomData,
+                     phantom_fu: PhantomData, }
+    }
+}
+
+impl<T, E, U, F> Debug for SwapResult<T, E, U, F> {
+    fn fmt(&self, fm: &mut Formatter) -> fmt::Result {
+        fm.debug_struct("SwapResult")
+          .field("phantom_te", &self.phantom_te)
+          .field("phantom_fu", &self.phantom_fu)
+          .finish()
+    }
+}
+
+impl<T, E, U, F> Clone for SwapResult<T, E, U, F> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, E, U, F> Copy for SwapResult<T, E, U, F> {}
+
+impl<T, E, U, F> Default for SwapResult<T, E, U, F> {
+    #[inline]
+    fn default() -> Self {
+        Self::mk()
+    }
+}
+
+impl<T, E, U, F> Lenticuloid for SwapResult<T, E, U, F> {
+    type InitialSource = Result<T, E>;
+
+    type InitialTarget = Result<E, T>;
+
+    type FinalSource = Result<U, F>;
+
+    type FinalTarget = Result<F, U>;
+
+    type AtInitial = SwapResult<T, E, T, E>;
+
+    fn at_initial(&self) -> Self::AtInitial {
+        SwapResult::mk()
+    }
+
+    type AtFinal = SwapResult<U, F, U, F>;
+
+    fn at_final(&self) -> Self::AtFinal {
+        SwapResult::mk()
+    }
+}
+
+impl_traversal_via_partial_lens!([T, E, U, F] SwapResult<T, E, U, F>);
+
+impl<T, E, U, F> PartialLens for SwapResult<T, E, U, F> {
+    type Context = ();
+
+    #[inline]
+    fn try_get(&self, v: Self::InitialSource) -> Result<Self::InitialTarget, Self::FinalSource> {
+        Ok(swap_result(v))
+    }
+
+    #[inline]
+    fn try_get_context(&self,
+                       v: Self::InitialSource)
+                       -> Result<(Self::InitialTarget, ()), Self::FinalSource> {
+        Ok((swap_result(v), ()))
+    }
+
+    #[inline]
+    fn fill_context(&self, _c: (), x: Self::FinalTarget) -> Self::FinalSource {
+        swap_result(x)
+    }
+
+    #[inline]
+    fn set(&self, _v: Self::InitialSource, x: Self::FinalTarget) -> Self::FinalSource {
+        swap_result(x)
+    }
+
+    #[inline]
+    fn exchange(&self,
+                v: Self::InitialSource,
+                x: Self::FinalTarget)
+                -> (Option<Self::InitialTarget>, Self::FinalSource) {
+        (Some(swap_result(v)), swap_result(x))
+    }
+
+    #[inline]
+    fn modify<G>(&self, v: Self::InitialSource, f: G) -> Self::FinalSource
+        where G: FnOnce(Self::InitialTarget) -> Self::FinalTarget
+    {
+        swap_result(f(swap_result(v)))
+    }
+
+    #[inline]
+    fn modify_with<G, X>(&self, v: Self::InitialSource, f: G) -> (Self::FinalSource, Option<X>)
+        where G: FnOnce(Self::InitialTarget) -> (Self::FinalTarget, X)
+    {
+        let (a, b) = f(swap_result(v));
+        (swap_result(a), Some(b))
+    }
+}
+
+impl_getter_via_lens!([T, E, U, F] SwapResult<T, E, U, F>);
+
+impl<T, E, U, F> Lens for SwapResult<T, E, U, F> {
+    #[inline]
+    fn get(&self, v: Self::InitialSource) -> Self::InitialTarget {
+        swap_result(v)
+    }
+}
+
+impl_review_via_prism!([T, E, U, F] SwapResult<T, E, U, F>);
+
+impl<T, E, U, F> Prism for SwapResult<T, E, U, F> {
+    #[inline]
+    fn inject(&self, v: Self::FinalTarget) -> Self::FinalSource {
+        swap_result(v)
+    }
+}
+
+impl<T, E, U, F> Iso for SwapResult<T, E, U, F> {}
+
+/// An `Iso` family viewing an `Option` as a `Result` with a `()` error.
+pub struct OptionAsResult<A, B = A> {
+    phantom_aa: PhantomData<OptionAsResultFn<A>>,
+    phantom_bb: PhantomData<ResultAsOptionFn<B>>,
+}
+
+impl<A, B> OptionAsResult<A, B> {
+    #[cfg(not(feature = "nightly"))]
+    #[inline]
+    pub fn mk() -> Self {
+        OptionAsResult { phantom_aa: PhantomData,
+                         phantom_bb: PhantomData, }
+    }
+
+    #[cfg(feature = "nightly")]
+    #[inline]
+    pub const fn mk() -> Self {
+        OptionAsResult { phantom_aa: PhantomData,
+                         phantom_bb: PhantomData, }
+    }
+}
+
+impl<A, B> Debug for OptionAsResult<A, B> {
+    fn fmt(&self, fm: &mut Formatter) -> fmt::Result {
+        fm.debug_struct("OptionAsResult")
+          .field("phantom_aa", &self.phantom_aa)
+          .field("phantom_bb", &self.phantom_bb)
+          .finish()
+    }
+}
+
+impl<A, B> Clone for OptionAsResult<A, B> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<A, B> Copy for OptionAsResult<A, B> {}
+
+impl<A, B> Default for OptionAsResult<A, B> {
+    #[inline]
+    fn default() -> Self {
+        Self::mk()
+    }
+}
+
+impl<A, B> Lenticuloid for OptionAsResult<A, B> {
+    type InitialSource = Option<A>;
+
+    type InitialTarget = Result<A, ()>;
+
+    type FinalSource = Option<B>;
+
+    type FinalTarget = Result<B, ()>;
+
+    type AtInitial = OptionAsResult<A, A>;
+
+    fn at_initial(&self) -> Self::AtInitial {
+        OptionAsResult::mk()
+    }
+
+    type AtFinal = OptionAsResult<B, B>;
+
+    fn at_final(&self) -> Self::AtFinal {
+        OptionAsResult::mk()
+    }
+}
+
+impl_traversal_via_partial_lens!([A, B] OptionAsResult<A, B>);
+
+impl<A, B> PartialLens for OptionAsResult<A, B> {
+    type Context = ();
+
+    #[inline]
+    fn try_get(&self, v: Self::InitialSource) -> Result<Self::InitialTarget, Self::FinalSource> {
+        Ok(option_to_result(v))
+    }
+
+    #[inline]
+    fn try_get_context(&self,
+                       v: Self::InitialSource)
+                       -> Result<(Self::InitialTarget, ()), Self::FinalSource> {
+        Ok((option_to_result(v), ()))
+    }
+
+    #[inline]
+    fn fill_context(&self, _c: (), x: Self::FinalTarget) -> Self::FinalSource {
+        result_to_option(x)
+    }
+
+    #[inline]
+    fn set(&self, _v: Self::InitialSource, x: Self::FinalTarget) -> Self::FinalSource {
+        result_to_option(x)
+    }
+
+    #[inline]
+    fn exchange(&self,
+                v: Self::InitialSource,
+                x: Self::FinalTarget)
+                -> (Option<Self::InitialTarget>, Self::FinalSource) {
+        (Some(option_to_result(v)), result_to_option(x))
+    }
+
+    #[inline]
+    fn modify<F>(&self, v: Self::InitialSource, f: F) -> Self::FinalSource
+        where F: FnOnce(Self::InitialTarget) -> Self::FinalTarget
+    {
+        result_to_option(f(option_to_result(v)))
+    }
+
+    #[inline]
+    fn modify_with<F, X>(&self, v: Self::InitialSource, f: F) -> (Self::FinalSource, Option<X>)
+        where F: FnOnce(Self::InitialTarget) -> (Self::FinalTarget, X)
+    {
+        let (a, b) = f(option_to_result(v));
+        (result_to_option(a), Some(b))
+    }
+}
+
+impl_getter_via_lens!([A, B] OptionAsResult<A, B>);
+
+impl<A, B> Lens for OptionAsResult<A, B> {
+    #[inline]
+    fn get(&self, v: Self::InitialSource) -> Self::InitialTarget {
+        option_to_result(v)
+    }
+}
+
+impl_review_via_prism!([A, B] OptionAsResult<A, B>);
+
+impl<A, B> Prism for OptionAsResult<A, B> {
+    #[inline]
+    fn inject(&self, v: Self::FinalTarget) -> Self::FinalSource {
+        result_to_option(v)
+    }
+}
+
+impl<A, B> Iso for OptionAsResult<A, B> {}
+
+/// An `Iso` family flattening a nested `Option` into a `Result` whose error
+/// records which layer was `None`: `Some(None)` becomes `Err(Some(()))` and
+/// `None` becomes `Err(None)`.
+pub struct FlattenOption<A, B = A> {
+    phantom_aa: PhantomData<FlattenOptionFn<A>>,
+    phantom_bb: PhantomData<UnflattenOptionFn<B>>,
+}
+
+impl<A, B> FlattenOption<A, B> {
+    #[cfg(not(feature = "nightly"))]
+    #[inline]
+    pub fn mk() -> Self {
+        FlattenOption { phantom_aa: PhantomData,
+                        phantom_bb: PhantomData, }
+    }
+
+    #[cfg(feature = "nightly")]
+    #[inline]
+    pub const fn mk() -> Self {
+        FlattenOption { phantom_aa: PhantomData,
+                        phantom_bb: PhantomData, }
+    }
+}
+
+impl<A, B> Debug for FlattenOption<A, B> {
+    fn fmt(&self, fm: &mut Formatter) -> fmt::Result {
+        fm.debug_struct("FlattenOption")
+          .field("phantom_aa", &self.phantom_aa)
+          .field("phantom_bb", &self.phantom_bb)
+          .finish()
+    }
+}
+
+impl<A, B> Clone for FlattenOption<A, B> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<A, B> Copy for FlattenOption<A, B> {}
+
+impl<A, B> Default for FlattenOption<A, B> {
+    #[inline]
+    fn default() -> Self {
+        Self::mk()
+    }
+}
+
+impl<A, B> Lenticuloid for FlattenOption<A, B> {
+    type InitialSource = Option<Option<A>>;
+
+    type InitialTarget = Result<A, Option<()>>;
+
+    type FinalSource = Option<Option<B>>;
+
+    type FinalTarget = Result<B, Option<()>>;
+
+    type AtInitial = FlattenOption<A, A>;
+
+    fn at_initial(&self) -> Self::AtInitial {
+        FlattenOption::mk()
+    }
+
+    type AtFinal = FlattenOption<B, B>;
+
+    fn at_final(&self) -> Self::AtFinal {
+        FlattenOption::mk()
+    }
+}
+
+impl_traversal_via_partial_lens!([A, B] FlattenOption<A, B>);
+
+impl<A, B> PartialLens for FlattenOption<A, B> {
+    type Context = ();
+
+    #[inline]
+    fn try_get(&self, v: Self::InitialSource) -> Result<Self::InitialTarget, Self::FinalSource> {
+        Ok(flatten_option(v))
+    }
+
+    #[inline]
+    fn try_get_context(&self,
+                       v: Self::InitialSource)
+                       -> Result<(Self::InitialTarget, ()), Self::FinalSource> {
+        Ok((flatten_option(v), ()))
+    }
+
+    #[inline]
+    fn fill_context(&self, _c: (), x: Self::FinalTarget) -> Self::FinalSource {
+        unflatten_option(x)
+    }
+
+    #[inline]
+    fn set(&self, _v: Self::InitialSource, x: Self::FinalTarget) -> Self::FinalSource {
+        unflatten_option(x)
+    }
+
+    #[inline]
+    fn exchange(&self,
+                v: Self::InitialSource,
+                x: Self::FinalTarget)
+                -> (Option<Self::InitialTarget>, Self::FinalSource) {
+        (Some(flatten_option(v)), unflatten_option(x))
+    }
+
+    #[inline]
+    fn modify<F>(&self, v: Self::InitialSource, f: F) -> Self::FinalSource
+        where F: FnOnce(Self::InitialTarget) -> Self::FinalTarget
+    {
+        unflatten_option(f(flatten_option(v)))
+    }
+
+    #[inline]
+    fn modify_with<F, X>(&self, v: Self::InitialSource, f: F) -> (Self::FinalSource, Option<X>)
+        where F: FnOnce(Self::InitialTarget) -> (Self::FinalTarget, X)
+    {
+        let (a, b) = f(flatten_option(v));
+        (unflatten_option(a), Some(b))
+    }
+}
+
+impl_getter_via_lens!([A, B] FlattenOption<A, B>);
+
+impl<A, B> Lens for FlattenOption<A, B> {
+    #[inline]
+    fn get(&self, v: Self::InitialSource) -> Self::InitialTarget {
+        flatten_option(v)
+    }
+}
+
+impl_review_via_prism!([A, B] FlattenOption<A, B>);
+
+impl<A, B> Prism for FlattenOption<A, B> {
+    #[inline]
+    fn inject(&self, v: Self::FinalTarget) -> Self::FinalSource {
+        unflatten_option(v)
+    }
+}
+
+impl<A, B> Iso for FlattenOption<A, B> {}
+
+/// An `Iso` family flattening a nested `Result` into one whose error records
+/// which layer failed: `Ok(Err(e))` becomes `Err(Ok(e))` and `Err(e)` becomes
+/// `Err(Err(e))`.
+pub struct FlattenResult<T, E1, E2, U = T, F1 = E1, F2 = E2> {
+    phantom_te: PhantomData<FlattenResultFn<T, E1, E2>>,
+    phantom_uf: PhantomData<UnflattenResultFn<U, F1, F2>>,
+}
+
+impl<T, E1, E2, U, F1, F2> FlattenResult<T, E1, E2, U, F1, F2> {
+    #[cfg(not(feature = "nightly"))]
+    #[inline]
+    pub fn mk() -> Self {
+        FlattenResult { phantom_te: PhantomData,
+                        phantom_uf: PhantomData, }
+    }
+
+    #[cfg(feature = "nightly")]
+    #[inline]
+    pub const fn mk() -> Self {
+        FlattenResult { phantom_te: PhantomData,
+                        phantom_uf: PhantomData, }
+    }
+}
+
+impl<T, E1, E2, U, F1, F2> Debug for FlattenResult<T, E1, E2, U, F1, F2> {
+    fn fmt(&self, fm: &mut Formatter) -> fmt::Result {
+        fm.debug_struct("FlattenResult")
+          .field("phantom_te", &self.phantom_te)
+          .field("phantom_uf", &self.phantom_uf)
+          .finish()
+    }
+}
+
+impl<T, E1, E2, U, F1, F2> Clone for FlattenResult<T, E1, E2, U, F1, F2> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, E1, E2, U, F1, F2> Copy for FlattenResult<T, E1, E2, U, F1, F2> {}
+
+impl<T, E1, E2, U, F1, F2> Default for FlattenResult<T, E1, E2, U, F1, F2> {
+    #[inline]
+    fn default() -> Self {
+        Self::mk()
+    }
+}
+
+impl<T, E1, E2, U, F1, F2> Lenticuloid for FlattenResult<T, E1, E2, U, F1, F2> {
+    type InitialSource = NestedResult<T, E1, E2>;
+
+    type InitialTarget = LayeredResult<T, E1, E2>;
+
+    type FinalSource = NestedResult<U, F1, F2>;
+
+    type FinalTarget = LayeredResult<U, F1, F2>;
+
+    type AtInitial = FlattenResult<T, E1, E2, T, E1, E2>;
+
+    fn at_initial(&self) -> Self::AtInitial {
+        FlattenResult::mk()
+    }
+
+    type AtFinal = FlattenResult<U, F1, F2, U, F1, F2>;
+
+    fn at_final(&self) -> Self::AtFinal {
+        FlattenResult::mk()
+    }
+}
+
+impl_traversal_via_partial_lens!([T, E1, E2, U, F1, F2] FlattenResult<T, E1, E2, U, F1, F2>);
+
+impl<T, E1, E2, U, F1, F2> PartialLens for FlattenResult<T, E1, E2, U, F1, F2> {
+    type Context = ();
+
+    #[inline]
+    fn try_get(&self, v: Self::InitialSource) -> Result<Self::InitialTarget, Self::FinalSource> {
+        Ok(flatten_result(v))
+    }
+
+    #[inline]
+    fn try_get_context(&self,
+                       v: Self::InitialSource)
+                       -> Result<(Self::InitialTarget, ()), Self::FinalSource> {
+        Ok((flatten_result(v), ()))
+    }
+
+    #[inline]
+    fn fill_context(&self, _c: (), x: Self::FinalTarget) -> Self::FinalSource {
+        unflatten_result(x)
+    }
+
+    #[inline]
+    fn set(&self, _v: Self::InitialSource, x: Self::FinalTarget) -> Self::FinalSource {
+        unflatten_result(x)
+    }
+
+    #[inline]
+    fn exchange(&self,
+                v: Self::InitialSource,
+                x: Self::FinalTarget)
+                -> (Option<Self::InitialTarget>, Self::FinalSource) {
+        (Some(flatten_result(v)), unflatten_result(x))
+    }
+
+    #[inline]
+    fn modify<F>(&self, v: Self::InitialSource, f: F) -> Self::FinalSource
+        where F: FnOnce(Self::InitialTarget) -> Self::FinalTarget
+    {
+        unflatten_result(f(flatten_result(v)))
+    }
+
+    #[inline]
+    fn modify_with<F, X>(&self, v: Self::InitialSource, f: F) -> (Self::FinalSource, Option<X>)
+        where F: FnOnce(Self::InitialTarget) -> (Self::FinalTarget, X)
+    {
+        let (a, b) = f(flatten_result(v));
+        (unflatten_result(a), Some(b))
+    }
+}
+
+impl_getter_via_lens!([T, E1, E2, U, F1, F2] FlattenResult<T, E1, E2, U, F1, F2>);
+
+impl<T, E1, E2, U, F1, F2> Lens for FlattenResult<T, E1, E2, U, F1, F2> {
+    #[inline]
+    fn get(&self, v: Self::InitialSource) -> Self::InitialTarget {
+        flatten_result(v)
+    }
+}
+
+impl_review_via_prism!([T, E1, E2, U, F1, F2] FlattenResult<T, E1, E2, U, F1, F2>);
+
+impl<T, E1, E2, U, F1, F2> Prism for FlattenResult<T, E1, E2, U, F1, F2> {
+    #[inline]
+    fn inject(&self, v: Self::FinalTarget) -> Self::FinalSource {
+        unflatten_result(v)
+    }
+}
+
+impl<T, E1, E2, U, F1, F2> Iso for FlattenResult<T, E1, E2, U, F1, F2> {}
+
+/// An `Iso` between `Option<A>` and `A` that treats a distinguished sentinel
+/// value as standing in for `None`; reading `None` yields the sentinel, and
+/// writing the sentinel yields `None`.
+#[derive(Clone,Copy,Debug,Default)]
+pub struct NonIso<A> {
+    sentinel: A,
+}
+
+impl<A: Clone + PartialEq> NonIso<A> {
+    #[inline]
+    pub fn of(a: A) -> Self {
+        NonIso { sentinel: a }
+    }
+
+    #[inline]
+    fn fill(&self, v: Option<A>) -> A {
+        v.unwrap_or_else(|| self.sentinel.clone())
+    }
+
+    #[inline]
+    fn unfill(&self, v: A) -> Option<A> {
+        if v == self.sentinel { None } else { Some(v) }
+    }
+}
+
+/// The sentinel-value isomorphism (function form).
+#[inline]
+pub fn non<A: Clone + PartialEq>(sentinel: A) -> NonIso<A> {
+    NonIso::of(sentinel)
+}
+
+impl<A: Clone + PartialEq> Lenticuloid for NonIso<A> {
+    type InitialSource = Option<A>;
+
+    type InitialTarget = A;
+
+    type FinalSource = Option<A>;
+
+    type FinalTarget = A;
+
+    type AtInitial = Self;
+
+    fn at_initial(&self) -> Self::AtInitial {
+        self.clone()
+    }
+
+    type AtFinal = Self;
+
+    fn at_final(&self) -> Self::AtFinal {
+        self.clone()
+    }
+}
+
+impl_traversal_via_partial_lens!([A: Clone + PartialEq] NonIso<A>);
+
+impl<A: Clone + PartialEq> PartialLens for NonIso<A> {
+    type Context = ();
+
+    #[inline]
+    fn try_get(&self, v: Option<A>) -> Result<A, Option<A>> {
+        Ok(self.fill(v))
+    }
+
+    #[inline]
+    fn try_get_context(&self, v: Option<A>) -> Result<(A, ()), Option<A>> {
+        Ok((self.fill(v), ()))
+    }
+
+    #[inline]
+    fn fill_context(&self, _c: (), x: A) -> Option<A> {
+        self.unfill(x)
+    }
+
+    #[inline]
+    fn set(&self, _v: Option<A>, x: A) -> Option<A> {
+        self.unfill(x)
+    }
+
+    #[inline]
+    fn exchange(&self, v: Option<A>, x: A) -> (Option<A>, Option<A>) {
+        (Some(self.fill(v)), self.unfill(x))
+    }
+
+    #[inline]
+    fn modify<F: FnOnce(A) -> A>(&self, v: Option<A>, f: F) -> Option<A> {
+        self.unfill(f(self.fill(v)))
+    }
+
+    #[inline]
+    fn modify_with<F, X>(&self, v: Option<A>, f: F) -> (Option<A>, Option<X>)
+        where F: FnOnce(A) -> (A, X)
+    {
+        let (a, b) = f(self.fill(v));
+        (self.unfill(a), Some(b))
+    }
+}
+
+impl_getter_via_lens!([A: Clone + PartialEq] NonIso<A>);
+
+impl<A: Clone + PartialEq> Lens for NonIso<A> {
+    #[inline]
+    fn get(&self, v: Option<A>) -> A {
+        self.fill(v)
+    }
+}
+
+impl_review_via_prism!([A: Clone + PartialEq] NonIso<A>);
+
+impl<A: Clone + PartialEq> Prism for NonIso<A> {
+    #[inline]
+    fn inject(&self, v: A) -> Option<A> {
+        self.unfill(v)
+    }
+}
+
+impl<A: Clone + PartialEq> Iso for NonIso<A> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::{ComposeExt, InvertExt, Lens, PartialLens, Prism};
+
+    #[test]
+    fn test_some_prism() {
+        let l = Some_::<u32, String>::mk();
+        assert_eq!(l.try_get(Some(3)), Ok(3));
+        assert_eq!(l.try_get(None), Err(None));
+        assert_eq!(l.modify(Some(3), |x| x.to_string()), Some("3".to_string()));
+        assert_eq!(l.inject("4".to_string()), Some("4".to_string()));
+        let (x, mut inj) = l.try_get_inject(Some(5)).ok().unwrap();
+        assert_eq!(x, 5);
+        assert_eq!(inj("6".to_string()), Some(Some("6".to_string())));
+        assert_eq!(inj("7".to_string()), None);
+    }
+
+    #[test]
+    fn test_ok_through_field() {
+        struct Config {
+            limit: Result<u32, String>,
+        }
+        let l = Ok_::<u32, u32, String>::mk().compose(field_lens!(Config => limit: Result<u32, String>));
+        let c = l.modify(Config { limit: Ok(4) }, |x| x * 2);
+        assert_eq!(c.limit, Ok(8));
+        let c = l.modify(Config { limit: Err("unset".to_string()) }, |x| x * 2);
+        assert_eq!(c.limit, Err("unset".to_string()));
+    }
+
+    #[test]
+    fn test_result_isos() {
+        let l = SwapResult::<u32, String>::mk();
+        assert_eq!(l.get(Ok(1)), Err(1));
+        assert_eq!(l.invert().get(Err(1)), Ok(1));
+        let l = FlattenResult::<u32, bool, char>::mk();
+        assert_eq!(l.get(Ok(Err(true))), Err(Ok(true)));
+        assert_eq!(l.inject(Err(Err('x'))), Err('x'));
+        let l = FlattenOption::<u32>::mk().compose(OptionAsResult::<Option<u32>>::mk().invert());
+        assert_eq!(l.get(Ok(Some(2))), Ok(2));
+        assert_eq!(l.get(Err(())), Err(None));
+        assert_eq!(l.inject(Err(Some(()))), Ok(None));
+    }
+
+    #[test]
+    fn test_non_iso() {
+        let l = non(0u32);
+        assert_eq!(l.modify(None, |x| x + 1), Some(1));
+        assert_eq!(l.modify(Some(1), |x| x - 1), None);
+        assert_eq!(l.get(None), 0);
+        assert_eq!(l.set(Some(3), 0), None);
+    }
+}
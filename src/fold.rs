@@ -0,0 +1,238 @@
+use std::iter::{self, Product, Sum};
+use super::access::Review;
+use super::{Compose, Identity, Invert, Lenticuloid};
+
+/// The supertype of all fold families.
+pub trait Fold: Lenticuloid
+    where Self::AtInitial: Fold,
+          Self::AtFinal: Fold
+{
+    fn for_each<F>(&self, v: Self::InitialSource, f: F)
+        where F: FnMut(Self::InitialTarget);
+
+    /// Visit each target in turn until `f` returns `false`, returning whether
+    /// every target was visited.  Implementations should stop traversing as
+    /// soon as they can; the default merely stops calling `f`.
+    fn for_each_while<F>(&self, v: Self::InitialSource, mut f: F) -> bool
+        where F: FnMut(Self::InitialTarget) -> bool
+    {
+        let mut going = true;
+        self.for_each(v, |x| if going {
+            going = f(x);
+        });
+        going
+    }
+
+    fn to_vec(&self, v: Self::InitialSource) -> Vec<Self::InitialTarget> {
+        let mut xs = Vec::new();
+        self.for_each(v, |x| xs.push(x));
+        xs
+    }
+}
+
+impl<S, T> Fold for Identity<S, T> {
+    #[inline]
+    fn for_each<F: FnMut(S)>(&self, v: S, mut f: F) {
+        f(v)
+    }
+
+    #[inline]
+    fn for_each_while<F: FnMut(S) -> bool>(&self, v: S, mut f: F) -> bool {
+        f(v)
+    }
+
+    #[inline]
+    fn to_vec(&self, v: S) -> Vec<S> {
+        vec![v]
+    }
+}
+
+impl<LF: Fold, LS: ?Sized> Fold for Compose<LF, LS>
+    where LS: Fold<InitialTarget = LF::InitialSource, FinalTarget = LF::FinalSource>,
+          LF::AtInitial: Fold,
+          LF::AtFinal: Fold,
+          LS::AtInitial: Fold,
+          LS::AtFinal: Fold
+{
+    fn for_each<F>(&self, v: Self::InitialSource, mut f: F)
+        where F: FnMut(Self::InitialTarget)
+    {
+        self.second.for_each(v, |q| self.first.for_each(q, &mut f))
+    }
+
+    fn for_each_while<F>(&self, v: Self::InitialSource, mut f: F) -> bool
+        where F: FnMut(Self::InitialTarget) -> bool
+    {
+        self.second.for_each_while(v, |q| self.first.for_each_while(q, &mut f))
+    }
+}
+
+impl<L: Review> Fold for Invert<L>
+    where L::AtInitial: Review,
+          L::AtFinal: Review
+{
+    #[inline]
+    fn for_each<F>(&self, v: Self::InitialSource, mut f: F)
+        where F: FnMut(Self::InitialTarget)
+    {
+        f(self.deinvert.review(v))
+    }
+}
+
+/// Collect every target of a fold.
+#[inline]
+pub fn to_vec_of<L: Fold>(l: &L, v: L::InitialSource) -> Vec<L::InitialTarget>
+    where L::AtInitial: Fold,
+          L::AtFinal: Fold
+{
+    l.to_vec(v)
+}
+
+/// Count the targets of a fold.
+pub fn length_of<L: Fold>(l: &L, v: L::InitialSource) -> usize
+    where L::AtInitial: Fold,
+          L::AtFinal: Fold
+{
+    let mut n = 0;
+    l.for_each(v, |_| n += 1);
+    n
+}
+
+/// Add up the targets of a fold.
+pub fn sum_of<L: Fold>(l: &L, v: L::InitialSource) -> L::InitialTarget
+    where L::AtInitial: Fold,
+          L::AtFinal: Fold,
+          L::InitialTarget: Sum
+{
+    let mut total = None;
+    l.for_each(v, |x| {
+        total = Some(match total.take() {
+            None => x,
+            Some(acc) => iter::once(acc).chain(iter::once(x)).sum(),
+        })
+    });
+    total.unwrap_or_else(|| iter::empty().sum())
+}
+
+/// Multiply together the targets of a fold.
+pub fn product_of<L: Fold>(l: &L, v: L::InitialSource) -> L::InitialTarget
+    where L::AtInitial: Fold,
+          L::AtFinal: Fold,
+          L::InitialTarget: Product
+{
+    let mut total = None;
+    l.for_each(v, |x| {
+        total = Some(match total.take() {
+            None => x,
+            Some(acc) => iter::once(acc).chain(iter::once(x)).product(),
+        })
+    });
+    total.unwrap_or_else(|| iter::empty().product())
+}
+
+/// Find the greatest target of a fold, if there are any; of several equally
+/// great targets, the last is returned.
+pub fn max_of<L: Fold>(l: &L, v: L::InitialSource) -> Option<L::InitialTarget>
+    where L::AtInitial: Fold,
+          L::AtFinal: Fold,
+          L::InitialTarget: Ord
+{
+    let mut best = None;
+    l.for_each(v, |x| match best {
+        Some(ref y) if x < *y => {}
+        _ => best = Some(x),
+    });
+    best
+}
+
+/// Find the least target of a fold, if there are any; of several equally
+/// least targets, the first is returned.
+pub fn min_of<L: Fold>(l: &L, v: L::InitialSource) -> Option<L::InitialTarget>
+    where L::AtInitial: Fold,
+          L::AtFinal: Fold,
+          L::InitialTarget: Ord
+{
+    let mut best = None;
+    l.for_each(v, |x| match best {
+        Some(ref y) if x >= *y => {}
+        _ => best = Some(x),
+    });
+    best
+}
+
+/// Check whether any target of a fold satisfies a predicate.
+pub fn any_of<L: Fold, P>(l: &L, v: L::InitialSource, mut p: P) -> bool
+    where L::AtInitial: Fold,
+          L::AtFinal: Fold,
+          P: FnMut(&L::InitialTarget) -> bool
+{
+    !l.for_each_while(v, |x| !p(&x))
+}
+
+/// Check whether every target of a fold satisfies a predicate.
+pub fn all_of<L: Fold, P>(l: &L, v: L::InitialSource, mut p: P) -> bool
+    where L::AtInitial: Fold,
+          L::AtFinal: Fold,
+          P: FnMut(&L::InitialTarget) -> bool
+{
+    l.for_each_while(v, |x| p(&x))
+}
+
+/// Find the first target of a fold satisfying a predicate.
+pub fn find_of<L: Fold, P>(l: &L, v: L::InitialSource, mut p: P) -> Option<L::InitialTarget>
+    where L::AtInitial: Fold,
+          L::AtFinal: Fold,
+          P: FnMut(&L::InitialTarget) -> bool
+{
+    let mut found = None;
+    l.for_each_while(v, |x| if p(&x) {
+        found = Some(x);
+        false
+    } else {
+        true
+    });
+    found
+}
+
+/// Find the first target of a fold, if there are any.
+pub fn first_of<L: Fold>(l: &L, v: L::InitialSource) -> Option<L::InitialTarget>
+    where L::AtInitial: Fold,
+          L::AtFinal: Fold
+{
+    find_of(l, v, |_| true)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::ComposeExt;
+    use collections::Each;
+
+    #[test]
+    fn test_aggregates() {
+        let l = Each::<Vec<u32>>::mk().compose(Each::<Vec<Vec<u32>>>::mk());
+        let orders = || vec![vec![3, 5], vec![], vec![2]];
+        assert_eq!(sum_of(&l, orders()), 10);
+        assert_eq!(product_of(&l, orders()), 30);
+        assert_eq!(length_of(&l, orders()), 3);
+        assert_eq!(max_of(&l, orders()), Some(5));
+        assert_eq!(min_of(&l, vec![]), None);
+        assert!(any_of(&l, orders(), |&x| x > 4));
+        assert!(!all_of(&l, orders(), |&x| x > 2));
+        assert_eq!(find_of(&l, orders(), |&x| x < 4), Some(3));
+        assert_eq!(first_of(&l, vec![]), None);
+        assert_eq!(to_vec_of(&l, orders()), vec![3, 5, 2]);
+    }
+
+    #[test]
+    fn test_short_circuit() {
+        let l = Each::<Vec<u32>>::mk().compose(Each::<Vec<Vec<u32>>>::mk());
+        let mut seen = Vec::new();
+        assert!(any_of(&l, vec![vec![3, 5], vec![7], vec![2]], |&x| {
+            seen.push(x);
+            x > 4
+        }));
+        assert_eq!(seen, vec![3, 5]);
+        assert_eq!(sum_of(&l, vec![]), 0);
+    }
+}
@@ -0,0 +1,598 @@
+//! These lenticuloids are built from ordinary functions, for when a full
+//! hand-written implementation would be overkill.
+//!
+//! Unlike most lenticuloids, they can't change types: each has
+//! `FinalSource = InitialSource` and `FinalTarget = InitialTarget`, and is
+//! its own `AtInitial` and `AtFinal`.  Those must work at a single pair of
+//! types, which a setter or builder that changes types can't.  For
+//! type-changing lenticuloids, use `field_lens!`, the `refraction-derive`
+//! derives, or a hand-written implementation.
+
+use std::fmt::{self, Debug, Formatter};
+use std::marker::PhantomData;
+use super::{Iso, Lens, Lenticuloid, PartialLens, Prism};
+
+/// A lens built from a getter and a setter, which keeps its types fixed.
+pub struct FnLens<S, A, G, P> {
+    getter: G,
+    setter: P,
+    phantom_sa: PhantomData<fn(S) -> A>,
+}
+
+impl<S, A, G, P> FnLens<S, A, G, P>
+    where G: Fn(&S) -> A,
+          P: Fn(S, A) -> S
+{
+    #[inline]
+    pub fn of(get: G, set: P) -> Self {
+        FnLens { getter: get,
+                 setter: set,
+                 phantom_sa: PhantomData, }
+    }
+}
+
+/// Build a lens from a getter and a setter (function form).
+#[inline]
+pub fn lens<S, A, G, P>(get: G, set: P) -> FnLens<S, A, G, P>
+    where G: Fn(&S) -> A,
+          P: Fn(S, A) -> S
+{
+    FnLens::of(get, set)
+}
+
+impl<S, A, G, P> Debug for FnLens<S, A, G, P> {
+    fn fmt(&self, fm: &mut Formatter) -> fmt::Result {
+        fm.debug_struct("FnLens")
+          .field("phantom_sa", &self.phantom_sa)
+          .finish()
+    }
+}
+
+impl<S, A, G: Clone, P: Clone> Clone for FnLens<S, A, G, P> {
+    #[inline]
+    fn clone(&self) -> Self {
+        FnLens { getter: self.getter.clone(),
+                 setter: self.setter.clone(),
+                 phantom_sa: PhantomData, }
+    }
+}
+
+impl<S, A, G: Copy, P: Copy> Copy for FnLens<S, A, G, P> {}
+
+impl<S, A, G, P> Lenticuloid for FnLens<S, A, G, P>
+    where G: Clone + Fn(&S) -> A,
+          P: Clone + Fn(S, A) -> S
+{
+    type InitialSource = S;
+
+    type InitialTarget = A;
+
+    type FinalSource = S;
+
+    type FinalTarget = A;
+
+    type AtInitial = Self;
+
+    fn at_initial(&self) -> Self::AtInitial {
+        self.clone()
+    }
+
+    type AtFinal = Self;
+
+    fn at_final(&self) -> Self::AtFinal {
+        self.clone()
+    }
+}
+
+impl_traversal_via_partial_lens!([S, A, G, P] FnLens<S, A, G, P>
+                                 where [G: Clone + Fn(&S) -> A,
+                                        P: Clone + Fn(S, A) -> S]);
+
+impl<S, A, G, P> PartialLens for FnLens<S, A, G, P>
+    where G: Clone + Fn(&S) -> A,
+          P: Clone + Fn(S, A) -> S
+{
+    type Context = S;
+
+    #[inline]
+    fn try_get(&self, v: S) -> Result<A, S> {
+        Ok((self.getter)(&v))
+    }
+
+    #[inline]
+    fn try_get_context(&self, v: S) -> Result<(A, S), S> {
+        Ok(((self.getter)(&v), v))
+    }
+
+    #[inline]
+    fn fill_context(&self, c: S, x: A) -> S {
+        (self.setter)(c, x)
+    }
+
+    #[inline]
+    fn set(&self, v: S, x: A) -> S {
+        (self.setter)(v, x)
+    }
+
+    #[inline]
+    fn modify_with<F, X>(&self, v: S, f: F) -> (S, Option<X>)
+        where F: FnOnce(A) -> (A, X)
+    {
+        let (x, aux) = f((self.getter)(&v));
+        ((self.setter)(v, x), Some(aux))
+    }
+}
+
+impl_getter_via_lens!([S, A, G, P] FnLens<S, A, G, P>
+                      where [G: Clone + Fn(&S) -> A,
+                             P: Clone + Fn(S, A) -> S]);
+
+impl<S, A, G, P> Lens for FnLens<S, A, G, P>
+    where G: Clone + Fn(&S) -> A,
+          P: Clone + Fn(S, A) -> S
+{
+    #[inline]
+    fn get(&self, v: S) -> A {
+        (self.getter)(&v)
+    }
+}
+
+/// A partial lens built from a fallible getter and a setter, which keeps its
+/// types fixed.
+pub struct FnPartialLens<S, A, G, P> {
+    getter: G,
+    setter: P,
+    phantom_sa: PhantomData<fn(S) -> A>,
+}
+
+impl<S, A, G, P> FnPartialLens<S, A, G, P>
+    where G: Fn(&S) -> Option<A>,
+          P: Fn(S, A) -> S
+{
+    #[inline]
+    pub fn of(try_get: G, set: P) -> Self {
+        FnPartialLens { getter: try_get,
+                        setter: set,
+                        phantom_sa: PhantomData, }
+    }
+}
+
+/// Build a partial lens from a fallible getter and a setter (function form).
+/// The setter is only ever called on sources the getter succeeded on.
+#[inline]
+pub fn partial_lens<S, A, G, P>(try_get: G, set: P) -> FnPartialLens<S, A, G, P>
+    where G: Fn(&S) -> Option<A>,
+          P: Fn(S, A) -> S
+{
+    FnPartialLens::of(try_get, set)
+}
+
+impl<S, A, G, P> Debug for FnPartialLens<S, A, G, P> {
+    fn fmt(&self, fm: &mut Formatter) -> fmt::Result {
+        fm.debug_struct("FnPartialLens")
+          .field("phantom_sa", &self.phantom_sa)
+          .finish()
+    }
+}
+
+impl<S, A, G: Clone, P: Clone> Clone for FnPartialLens<S, A, G, P> {
+    #[inline]
+    fn clone(&self) -> Self {
+        FnPartialLens { getter: self.getter.clone(),
+                        setter: self.setter.clone(),
+                        phantom_sa: PhantomData, }
+    }
+}
+
+impl<S, A, G: Copy, P: Copy> Copy for FnPartialLens<S, A, G, P> {}
+
+impl<S, A, G, P> Lenticuloid for FnPartialLens<S, A, G, P>
+    where G: Clone + Fn(&S) -> Option<A>,
+          P: Clone + Fn(S, A) -> S
+{
+    type InitialSource = S;
+
+    type InitialTarget = A;
+
+    type FinalSource = S;
+
+    type FinalTarget = A;
+
+    type AtInitial = Self;
+
+    fn at_initial(&self) -> Self::AtInitial {
+        self.clone()
+    }
+
+    type AtFinal = Self;
+
+    fn at_final(&self) -> Self::AtFinal {
+        self.clone()
+    }
+}
+
+impl_traversal_via_partial_lens!([S, A, G, P] FnPartialLens<S, A, G, P>
+                                 where [G: Clone + Fn(&S) -> Option<A>,
+                                        P: Clone + Fn(S, A) -> S]);
+
+impl<S, A, G, P> PartialLens for FnPartialLens<S, A, G, P>
+    where G: Clone + Fn(&S) -> Option<A>,
+          P: Clone + Fn(S, A) -> S
+{
+    type Context = S;
+
+    #[inline]
+    fn try_get(&self, v: S) -> Result<A, S> {
+        match (self.getter)(&v) {
+            Some(x) => Ok(x),
+            None => Err(v),
+        }
+    }
+
+    #[inline]
+    fn try_get_context(&self, v: S) -> Result<(A, S), S> {
+        match (self.getter)(&v) {
+            Some(x) => Ok((x, v)),
+            None => Err(v),
+        }
+    }
+
+    #[inline]
+    fn fill_context(&self, c: S, x: A) -> S {
+        (self.setter)(c, x)
+    }
+
+    #[inline]
+    fn modify_with<F, X>(&self, v: S, f: F) -> (S, Option<X>)
+        where F: FnOnce(A) -> (A, X)
+    {
+        match (self.getter)(&v) {
+            Some(x) => {
+                let (x, aux) = f(x);
+                ((self.setter)(v, x), Some(aux))
+            }
+            None => (v, None),
+        }
+    }
+}
+
+/// A prism built from a matcher and a builder, which keeps its types fixed.
+pub struct FnPrism<S, A, M, B> {
+    matcher: M,
+    builder: B,
+    phantom_sa: PhantomData<fn(S) -> A>,
+}
+
+impl<S, A, M, B> FnPrism<S, A, M, B>
+    where M: Fn(S) -> Result<A, S>,
+          B: Fn(A) -> S
+{
+    #[inline]
+    pub fn of(try_get: M, inject: B) -> Self {
+        FnPrism { matcher: try_get,
+                  builder: inject,
+                  phantom_sa: PhantomData, }
+    }
+}
+
+/// Build a prism from a matcher and a builder (function form).
+#[inline]
+pub fn prism<S, A, M, B>(try_get: M, inject: B) -> FnPrism<S, A, M, B>
+    where M: Fn(S) -> Result<A, S>,
+          B: Fn(A) -> S
+{
+    FnPrism::of(try_get, inject)
+}
+
+impl<S, A, M, B> Debug for FnPrism<S, A, M, B> {
+    fn fmt(&self, fm: &mut Formatter) -> fmt::Result {
+        fm.debug_struct("FnPrism")
+          .field("phantom_sa", &self.phantom_sa)
+          .finish()
+    }
+}
+
+impl<S, A, M: Clone, B: Clone> Clone for FnPrism<S, A, M, B> {
+    #[inline]
+    fn clone(&self) -> Self {
+        FnPrism { matcher: self.matcher.clone(),
+                  builder: self.builder.clone(),
+                  phantom_sa: PhantomData, }
+    }
+}
+
+impl<S, A, M: Copy, B: Copy> Copy for FnPrism<S, A, M, B> {}
+
+impl<S, A, M, B> Lenticuloid for FnPrism<S, A, M, B>
+    where M: Clone + Fn(S) -> Result<A, S>,
+          B: Clone + Fn(A) -> S
+{
+    type InitialSource = S;
+
+    type InitialTarget = A;
+
+    type FinalSource = S;
+
+    type FinalTarget = A;
+
+    type AtInitial = Self;
+
+    fn at_initial(&self) -> Self::AtInitial {
+        self.clone()
+    }
+
+    type AtFinal = Self;
+
+    fn at_final(&self) -> Self::AtFinal {
+        self.clone()
+    }
+}
+
+impl_traversal_via_partial_lens!([S, A, M, B] FnPrism<S, A, M, B>
+                                 where [M: Clone + Fn(S) -> Result<A, S>,
+                                        B: Clone + Fn(A) -> S]);
+
+impl<S, A, M, B> PartialLens for FnPrism<S, A, M, B>
+    where M: Clone + Fn(S) -> Result<A, S>,
+          B: Clone + Fn(A) -> S
+{
+    type Context = ();
+
+    #[inline]
+    fn try_get(&self, v: S) -> Result<A, S> {
+        (self.matcher)(v)
+    }
+
+    #[inline]
+    fn try_get_context(&self, v: S) -> Result<(A, ()), S> {
+        (self.matcher)(v).map(|x| (x, ()))
+    }
+
+    #[inline]
+    fn fill_context(&self, _c: (), x: A) -> S {
+        (self.builder)(x)
+    }
+
+    #[inline]
+    fn set(&self, v: S, x: A) -> S {
+        match (self.matcher)(v) {
+            Ok(_) => (self.builder)(x),
+            Err(v) => v,
+        }
+    }
+
+    #[inline]
+    fn modify_with<F, X>(&self, v: S, f: F) -> (S, Option<X>)
+        where F: FnOnce(A) -> (A, X)
+    {
+        match (self.matcher)(v) {
+            Ok(x) => {
+                let (x, aux) = f(x);
+                ((self.builder)(x), Some(aux))
+            }
+            Err(v) => (v, None),
+        }
+    }
+}
+
+impl_review_via_prism!([S, A, M, B] FnPrism<S, A, M, B>
+                       where [M: Clone + Fn(S) -> Result<A, S>,
+                              B: Clone + Fn(A) -> S]);
+
+impl<S, A, M, B> Prism for FnPrism<S, A, M, B>
+    where M: Clone + Fn(S) -> Result<A, S>,
+          B: Clone + Fn(A) -> S
+{
+    #[inline]
+    fn inject(&self, v: A) -> S {
+        (self.builder)(v)
+    }
+}
+
+/// An isomorphism built from a pair of mutually inverse functions, which
+/// keeps its types fixed.
+pub struct FnIso<S, A, F, G> {
+    forward: F,
+    backward: G,
+    phantom_sa: PhantomData<fn(S) -> A>,
+}
+
+impl<S, A, F, G> FnIso<S, A, F, G>
+    where F: Fn(S) -> A,
+          G: Fn(A) -> S
+{
+    #[inline]
+    pub fn of(to: F, from: G) -> Self {
+        FnIso { forward: to,
+                backward: from,
+                phantom_sa: PhantomData, }
+    }
+}
+
+/// Build an isomorphism from a pair of mutually inverse functions (function
+/// form).
+#[inline]
+pub fn iso<S, A, F, G>(to: F, from: G) -> FnIso<S, A, F, G>
+    where F: Fn(S) -> A,
+          G: Fn(A) -> S
+{
+    FnIso::of(to, from)
+}
+
+impl<S, A, F, G> Debug for FnIso<S, A, F, G> {
+    fn fmt(&self, fm: &mut Formatter) -> fmt::Result {
+        fm.debug_struct("FnIso")
+          .field("phantom_sa", &self.phantom_sa)
+          .finish()
+    }
+}
+
+impl<S, A, F: Clone, G: Clone> Clone for FnIso<S, A, F, G> {
+    #[inline]
+    fn clone(&self) -> Self {
+        FnIso { forward: self.forward.clone(),
+                backward: self.backward.clone(),
+                phantom_sa: PhantomData, }
+    }
+}
+
+impl<S, A, F: Copy, G: Copy> Copy for FnIso<S, A, F, G> {}
+
+impl<S, A, F, G> Lenticuloid for FnIso<S, A, F, G>
+    where F: Clone + Fn(S) -> A,
+          G: Clone + Fn(A) -> S
+{
+    type InitialSource = S;
+
+    type InitialTarget = A;
+
+    type FinalSource = S;
+
+    type FinalTarget = A;
+
+    type AtInitial = Self;
+
+    fn at_initial(&self) -> Self::AtInitial {
+        self.clone()
+    }
+
+    type AtFinal = Self;
+
+    fn at_final(&self) -> Self::AtFinal {
+        self.clone()
+    }
+}
+
+impl_traversal_via_partial_lens!([S, A, F, G] FnIso<S, A, F, G>
+                                 where [F: Clone + Fn(S) -> A,
+                                        G: Clone + Fn(A) -> S]);
+
+impl<S, A, F, G> PartialLens for FnIso<S, A, F, G>
+    where F: Clone + Fn(S) -> A,
+          G: Clone + Fn(A) -> S
+{
+    type Context = ();
+
+    #[inline]
+    fn try_get(&self, v: S) -> Result<A, S> {
+        Ok((self.forward)(v))
+    }
+
+    #[inline]
+    fn try_get_context(&self, v: S) -> Result<(A, ()), S> {
+        Ok(((self.forward)(v), ()))
+    }
+
+    #[inline]
+    fn fill_context(&self, _c: (), x: A) -> S {
+        (self.backward)(x)
+    }
+
+    #[inline]
+    fn set(&self, _v: S, x: A) -> S {
+        (self.backward)(x)
+    }
+
+    #[inline]
+    fn modify<H: FnOnce(A) -> A>(&self, v: S, f: H) -> S {
+        (self.backward)(f((self.forward)(v)))
+    }
+
+    #[inline]
+    fn modify_with<H, X>(&self, v: S, f: H) -> (S, Option<X>)
+        where H: FnOnce(A) -> (A, X)
+    {
+        let (x, aux) = f((self.forward)(v));
+        ((self.backward)(x), Some(aux))
+    }
+}
+
+impl_getter_via_lens!([S, A, F, G] FnIso<S, A, F, G>
+                      where [F: Clone + Fn(S) -> A,
+                             G: Clone + Fn(A) -> S]);
+
+impl<S, A, F, G> Lens for FnIso<S, A, F, G>
+    where F: Clone + Fn(S) -> A,
+          G: Clone + Fn(A) -> S
+{
+    #[inline]
+    fn get(&self, v: S) -> A {
+        (self.forward)(v)
+    }
+}
+
+impl_review_via_prism!([S, A, F, G] FnIso<S, A, F, G>
+                       where [F: Clone + Fn(S) -> A,
+                              G: Clone + Fn(A) -> S]);
+
+impl<S, A, F, G> Prism for FnIso<S, A, F, G>
+    where F: Clone + Fn(S) -> A,
+          G: Clone + Fn(A) -> S
+{
+    #[inline]
+    fn inject(&self, v: A) -> S {
+        (self.backward)(v)
+    }
+}
+
+impl<S, A, F, G> Iso for FnIso<S, A, F, G>
+    where F: Clone + Fn(S) -> A,
+          G: Clone + Fn(A) -> S
+{
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::{ComposeExt, InvertExt, Lens, PartialLens, Prism};
+    use access::Review;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[test]
+    fn test_fn_lens() {
+        let l = lens(|p: &Point| p.x, |p: Point, x| Point { x, ..p });
+        assert_eq!(l.get(Point { x: 1, y: 2 }), 1);
+        assert_eq!(l.modify(Point { x: 1, y: 2 }, |x| x + 10), Point { x: 11, y: 2 });
+        let (x, mut inj) = l.try_get_inject(Point { x: 3, y: 4 }).unwrap();
+        assert_eq!(x, 3);
+        assert_eq!(inj(5), Some(Point { x: 5, y: 4 }));
+        assert_eq!(inj(6), None);
+    }
+
+    #[test]
+    fn test_fn_partial_lens() {
+        let l = partial_lens(|v: &Vec<u8>| v.first().cloned(),
+                             |mut v: Vec<u8>, x| {
+                                 v[0] = x;
+                                 v
+                             });
+        assert_eq!(l.try_get(vec![1, 2]), Ok(1));
+        assert_eq!(l.try_get(vec![]), Err(vec![]));
+        assert_eq!(l.modify(vec![1, 2], |x| x * 3), vec![3, 2]);
+        assert_eq!(l.modify(vec![], |x| x * 3), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn test_fn_prism() {
+        let p = prism(|v: Result<u8, String>| v.map_err(Err), Ok);
+        assert_eq!(p.try_get(Ok(1)), Ok(1));
+        assert_eq!(p.try_get(Err("e".to_string())), Err(Err("e".to_string())));
+        assert_eq!(p.inject(2), Ok(2));
+        assert_eq!(p.review(3), Ok(3));
+    }
+
+    #[test]
+    fn test_fn_iso() {
+        let i = iso(|x: i32| -x, |x: i32| -x);
+        assert_eq!(i.get(1), -1);
+        assert_eq!(i.inject(2), -2);
+        let l = i.compose(lens(|p: &Point| p.y, |p: Point, y| Point { y, ..p }));
+        assert_eq!(l.modify(Point { x: 1, y: 2 }, |y| y + 1), Point { x: 1, y: 1 });
+        assert_eq!(i.invert().at_final().get(5), -5);
+    }
+}
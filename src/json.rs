@@ -0,0 +1,367 @@
+//! These lenticuloids take apart and rebuild `serde_json::Value`s, so that
+//! JSON documents can be patched in place with `PartialLens::modify` and
+//! friends.  `Value` is a `dynamic::Tree`, so `dynamic::Path`s address JSON
+//! documents too.  `Key` and `Nth` are the `dynamic` module's `Key` and
+//! `Index` over `Value`, and so are built with `Key::of("name")` and
+//! `Nth::of(0)` rather than being tuple structs.  This module is only
+//! available with the `serde_json` cargo feature.
+
+use std::fmt::{self, Debug, Formatter};
+use std::marker::PhantomData;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Number, Value};
+use super::dynamic;
+use super::{Lenticuloid, PartialLens, Prism};
+
+macro_rules! value_prism {
+    ($(#[$attr:meta])* $name:ident: $target:ty,
+     |$v:ident| $matcher:expr,
+     |$x:ident| $builder:expr) => {
+        $(#[$attr])*
+        #[derive(Clone,Copy,Debug,Default,PartialEq,Eq,Hash)]
+        pub struct $name;
+
+        impl $name {
+            #[inline]
+            pub fn mk() -> Self {
+                $name
+            }
+        }
+
+        impl Lenticuloid for $name {
+            type InitialSource = Value;
+
+            type InitialTarget = $target;
+
+            type FinalSource = Value;
+
+            type FinalTarget = $target;
+
+            type AtInitial = Self;
+
+            fn at_initial(&self) -> Self::AtInitial {
+                *self
+            }
+
+            type AtFinal = Self;
+
+            fn at_final(&self) -> Self::AtFinal {
+                *self
+            }
+        }
+
+        impl_traversal_via_partial_lens!([] $name);
+
+        impl PartialLens for $name {
+            type Context = ();
+
+            #[inline]
+            fn try_get(&self, $v: Value) -> Result<$target, Value> {
+                $matcher
+            }
+
+            #[inline]
+            fn try_get_context(&self, v: Value) -> Result<($target, ()), Value> {
+                self.try_get(v).map(|x| (x, ()))
+            }
+
+            #[inline]
+            fn fill_context(&self, _c: (), x: $target) -> Value {
+                self.inject(x)
+            }
+
+            #[inline]
+            fn modify_with<F, X>(&self, v: Value, f: F) -> (Value, Option<X>)
+                where F: FnOnce($target) -> ($target, X)
+            {
+                match self.try_get(v) {
+                    Ok(x) => {
+                        let (x, aux) = f(x);
+                        (self.inject(x), Some(aux))
+                    }
+                    Err(v) => (v, None),
+                }
+            }
+        }
+
+        impl_review_via_prism!([] $name);
+
+        impl Prism for $name {
+            #[inline]
+            fn inject(&self, $x: $target) -> Value {
+                $builder
+            }
+        }
+    };
+}
+
+value_prism! {
+    /// A prism onto the members of an object.
+    AsObject: Map<String, Value>,
+    |v| match v {
+        Value::Object(m) => Ok(m),
+        v => Err(v),
+    },
+    |x| Value::Object(x)
+}
+
+value_prism! {
+    /// A prism onto the elements of an array.
+    AsArray: Vec<Value>,
+    |v| match v {
+        Value::Array(a) => Ok(a),
+        v => Err(v),
+    },
+    |x| Value::Array(x)
+}
+
+value_prism! {
+    /// A prism onto the contents of a string.
+    AsStr: String,
+    |v| match v {
+        Value::String(s) => Ok(s),
+        v => Err(v),
+    },
+    |x| Value::String(x)
+}
+
+value_prism! {
+    /// A prism onto numbers that fit in an `i64`.
+    AsI64: i64,
+    |v| match v.as_i64() {
+        Some(n) => Ok(n),
+        None => Err(v),
+    },
+    |x| Value::Number(Number::from(x))
+}
+
+value_prism! {
+    /// A prism onto numbers written with a fraction or exponent, as `f64`s.
+    /// Integers are left to `AsI64`, so that they come back out as integers.
+    /// JSON has no infinities or NaN, so, as with `Value::from`, injecting one
+    /// of those yields `null`.
+    AsF64: f64,
+    |v| match v {
+        Value::Number(ref n) if n.is_f64() => Ok(n.as_f64().unwrap()),
+        v => Err(v),
+    },
+    |x| Value::from(x)
+}
+
+value_prism! {
+    /// A prism onto numbers of any kind.
+    AsNumber: Number,
+    |v| match v {
+        Value::Number(n) => Ok(n),
+        v => Err(v),
+    },
+    |x| Value::Number(x)
+}
+
+value_prism! {
+    /// A prism onto booleans.
+    AsBool: bool,
+    |v| match v {
+        Value::Bool(b) => Ok(b),
+        v => Err(v),
+    },
+    |x| Value::Bool(x)
+}
+
+value_prism! {
+    /// A prism onto `null`.
+    AsNull: (),
+    |v| match v {
+        Value::Null => Ok(()),
+        v => Err(v),
+    },
+    |_x| Value::Null
+}
+
+impl_tree!(Value);
+
+/// A partial lens onto the member of an object with a given key.
+pub type Key = dynamic::Key<Value>;
+
+/// A partial lens onto the element of an array at a given index.
+pub type Nth = dynamic::Index<Value>;
+
+/// A prism onto the values that deserialize as a `T`.
+///
+/// # Panics
+///
+/// Injecting a `T` whose `Serialize` implementation fails, such as a map with
+/// non-string keys, panics.
+pub struct Typed<T> {
+    phantom_tt: PhantomData<fn(T) -> T>,
+}
+
+impl<T: Serialize + DeserializeOwned> Typed<T> {
+    #[inline]
+    pub fn mk() -> Self {
+        Typed { phantom_tt: PhantomData }
+    }
+}
+
+impl<T> Debug for Typed<T> {
+    fn fmt(&self, fm: &mut Formatter) -> fmt::Result {
+        fm.debug_struct("Typed")
+          .field("phantom_tt", &self.phantom_tt)
+          .finish()
+    }
+}
+
+// Not derived, since that would demand `T: Clone` and `T: Copy`.
+impl<T> Clone for Typed<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Typed<T> {}
+
+impl<T: Serialize + DeserializeOwned> Default for Typed<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::mk()
+    }
+}
+
+impl<T: Serialize + DeserializeOwned> Lenticuloid for Typed<T> {
+    type InitialSource = Value;
+
+    type InitialTarget = T;
+
+    type FinalSource = Value;
+
+    type FinalTarget = T;
+
+    type AtInitial = Self;
+
+    fn at_initial(&self) -> Self::AtInitial {
+        *self
+    }
+
+    type AtFinal = Self;
+
+    fn at_final(&self) -> Self::AtFinal {
+        *self
+    }
+}
+
+impl_traversal_via_partial_lens!([T: Serialize + DeserializeOwned] Typed<T>);
+
+impl<T: Serialize + DeserializeOwned> PartialLens for Typed<T> {
+    type Context = ();
+
+    #[inline]
+    fn try_get(&self, v: Value) -> Result<T, Value> {
+        // Deserializing from a borrow leaves `v` intact to hand back on failure.
+        T::deserialize(&v).map_err(|_| v)
+    }
+
+    #[inline]
+    fn try_get_context(&self, v: Value) -> Result<(T, ()), Value> {
+        self.try_get(v).map(|x| (x, ()))
+    }
+
+    #[inline]
+    fn fill_context(&self, _c: (), x: T) -> Value {
+        self.inject(x)
+    }
+
+    fn modify_with<F, X>(&self, v: Value, f: F) -> (Value, Option<X>)
+        where F: FnOnce(T) -> (T, X)
+    {
+        match self.try_get(v) {
+            Ok(x) => {
+                let (x, aux) = f(x);
+                (self.inject(x), Some(aux))
+            }
+            Err(v) => (v, None),
+        }
+    }
+}
+
+impl_review_via_prism!([T: Serialize + DeserializeOwned] Typed<T>);
+
+impl<T: Serialize + DeserializeOwned> Prism for Typed<T> {
+    #[inline]
+    fn inject(&self, v: T) -> Value {
+        ::serde_json::to_value(v).expect("value could not be serialized as JSON")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+    use serde_json::Value;
+    use super::*;
+    use ::ComposeExt;
+    use access::Review;
+    use dynamic::Path;
+
+    fn doc() -> Value {
+        ::serde_json::from_str(r#"{"name": "web", "ports": [80, 443], "tls": true, "extra": null}"#)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_scalar_prisms() {
+        assert_eq!(AsBool.try_get(Value::Bool(true)), Ok(true));
+        assert_eq!(AsStr.try_get(Value::Bool(true)), Err(Value::Bool(true)));
+        assert_eq!(AsI64.inject(3), Value::from(3));
+        assert_eq!(AsF64.try_get(Value::from(2.5)), Ok(2.5));
+        assert_eq!(AsF64.try_get(Value::from(3)), Err(Value::from(3)));
+        assert_eq!(AsF64.try_get(AsF64.inject(80.0)), Ok(80.0));
+        assert_eq!(AsF64.inject(f64::NAN), Value::Null);
+        assert_eq!(AsNumber.try_get(Value::from(3)), Ok(Number::from(3)));
+        assert_eq!(AsNull.try_get(Value::Null), Ok(()));
+        assert_eq!(AsArray.modify(Value::from(vec![1, 2]), |mut a| {
+                       a.pop();
+                       a
+                   }),
+                   Value::from(vec![1]));
+        assert_eq!(AsObject.try_get(doc()).map(|m| m.len()), Ok(4));
+    }
+
+    #[test]
+    fn test_patch_in_place() {
+        let port = AsI64.compose(Nth::of(1)).compose(Key::of("ports"));
+        let v = port.modify(doc(), |p| p + 8000);
+        assert_eq!(v["ports"], Value::from(vec![80, 8443]));
+        assert_eq!(Nth::of(2).compose(Key::of("ports")).try_get(v.clone()), Err(v.clone()));
+        let name = AsStr.compose(Key::of("name"));
+        let (s, c) = name.try_get_context(v).ok().unwrap();
+        assert_eq!(s, "web");
+        assert_eq!(name.fill_context(c, s + "-1")["name"], Value::from("web-1"));
+    }
+
+    #[test]
+    fn test_path_lens() {
+        let p: Path = "ports[0]".parse().unwrap();
+        let l = p.lens();
+        assert_eq!(l.try_get(doc()), Ok(Value::from(80)));
+        assert_eq!(l.set(doc(), Value::from(81))["ports"], Value::from(vec![81, 443]));
+        assert_eq!(Path::parse("tls[0]").unwrap().lens().try_get(doc()), Err(doc()));
+        assert_eq!(Path::parse("ports[2]").unwrap().get(&doc()).unwrap_err().depth(), 1);
+    }
+
+    #[test]
+    fn test_typed() {
+        let ports = Typed::<Vec<u16>>::mk().compose(Key::of("ports"));
+        assert_eq!(ports.try_get(doc()), Ok(vec![80, 443]));
+        let v = ports.modify(doc(), |mut ps| {
+            ps.push(8080);
+            ps
+        });
+        assert_eq!(v["ports"], Value::from(vec![80, 443, 8080]));
+        let t = Typed::<BTreeMap<String, bool>>::mk();
+        // `Typed` is `Copy` even when its target isn't.
+        let u = t;
+        assert_eq!(t.try_get(Value::from(1)), Err(Value::from(1)));
+        assert_eq!(u.review(BTreeMap::new()), Value::Object(Map::new()));
+    }
+}
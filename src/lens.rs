@@ -1,11 +1,24 @@
+use super::access::{AsGetter, Getter};
 use super::{Compose, Identity, Invert, Iso, PartialLens};
 
 /// The supertype of all lens families.
-pub trait Lens: PartialLens
+///
+/// `Getter` is a required supertrait, which
+/// [`impl_getter_via_lens!`](::impl_getter_via_lens!) provides in terms of
+/// this trait.
+pub trait Lens: PartialLens + Getter
     where Self::AtInitial: Lens,
           Self::AtFinal: Lens
 {
     fn get(&self, v: Self::InitialSource) -> Self::InitialTarget;
+
+    /// Forget this lens's ability to set, leaving only a `Getter`.
+    #[allow(clippy::wrong_self_convention)]
+    fn as_getter(self) -> AsGetter<Self>
+        where Self: Sized
+    {
+        AsGetter::of(self)
+    }
 }
 
 impl<S, T> Lens for Identity<S, T> {
@@ -36,3 +49,70 @@ impl<L: Iso> Lens for Invert<L>
         self.deinvert.inject(v)
     }
 }
+
+/// The supertype of all lens families that can borrow their target from a
+/// borrowed source.  The `Self: 'a` bounds let a composite borrow through its
+/// intermediate types.
+pub trait LensRef: Lens
+    where Self::AtInitial: LensRef,
+          Self::AtFinal: LensRef
+{
+    fn view_ref<'a>(&self, v: &'a Self::InitialSource) -> &'a Self::InitialTarget
+        where Self: 'a;
+}
+
+impl<S, T> LensRef for Identity<S, T> {
+    #[inline]
+    fn view_ref<'a>(&self, v: &'a S) -> &'a S
+        where Self: 'a
+    {
+        v
+    }
+}
+
+impl<LF: LensRef, LS: ?Sized> LensRef for Compose<LF, LS>
+    where LS: LensRef<InitialTarget = LF::InitialSource, FinalTarget = LF::FinalSource>,
+          LF::AtInitial: LensRef,
+          LF::AtFinal: LensRef,
+          LS::AtInitial: LensRef,
+          LS::AtFinal: LensRef
+{
+    fn view_ref<'a>(&self, v: &'a Self::InitialSource) -> &'a Self::InitialTarget
+        where Self: 'a
+    {
+        self.first.view_ref(self.second.view_ref(v))
+    }
+}
+
+/// The supertype of all lens families that can mutably borrow their target
+/// from a mutably borrowed source, for updating it in place.
+pub trait LensMut: LensRef
+    where Self::AtInitial: LensMut,
+          Self::AtFinal: LensMut
+{
+    fn view_mut<'a>(&self, v: &'a mut Self::InitialSource) -> &'a mut Self::InitialTarget
+        where Self: 'a;
+}
+
+impl<S, T> LensMut for Identity<S, T> {
+    #[inline]
+    fn view_mut<'a>(&self, v: &'a mut S) -> &'a mut S
+        where Self: 'a
+    {
+        v
+    }
+}
+
+impl<LF: LensMut, LS: ?Sized> LensMut for Compose<LF, LS>
+    where LS: LensMut<InitialTarget = LF::InitialSource, FinalTarget = LF::FinalSource>,
+          LF::AtInitial: LensMut,
+          LF::AtFinal: LensMut,
+          LS::AtInitial: LensMut,
+          LS::AtFinal: LensMut
+{
+    fn view_mut<'a>(&self, v: &'a mut Self::InitialSource) -> &'a mut Self::InitialTarget
+        where Self: 'a
+    {
+        self.first.view_mut(self.second.view_mut(v))
+    }
+}
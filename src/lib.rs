@@ -8,7 +8,19 @@
 //!
 //! - Lenticuloids that deal with the `!` type
 //! - `const fn` support
-//! - More efficient implementation of some macros
+//!
+//! The `serde_json` cargo feature flag enables the [`json`](json/index.html)
+//! module, of lenticuloids over `serde_json::Value`.  The `cli` feature
+//! additionally builds `refract`, a `jq`-like tool for reading and editing
+//! JSON documents through lens paths.
+//!
+//! Every `PartialLens` is also a `Traversal`, and so a `Fold` and a `Setter`;
+//! this is a breaking change for implementors outside this crate, who can
+//! derive the extra impls with
+//! [`impl_traversal_via_partial_lens!`].  Those impls can't be blanket ones,
+//! since a blanket impl over every `PartialLens` would overlap the ones that
+//! let `Compose` chain traversals like `collections::Each`, which are not
+//! partial lenses.
 //!
 //! To get started easily, import
 //! [`refraction::prelude::*`](prelude/index.html).
@@ -16,33 +28,122 @@
 #![cfg_attr(feature = "nightly", feature(never_type, const_fn))]
 #![cfg_attr(feature = "cargo-clippy", allow(expl_impl_clone_on_copy, type_complexity))]
 
+#[cfg(feature = "serde_json")]
+extern crate serde;
+#[cfg(feature = "serde_json")]
+extern crate serde_json;
+
 use std::fmt;
 use std::marker::PhantomData;
-extern crate nodrop;
 
 /// A [prelude module](https://doc.rust-lang.org/std/prelude/#other-preludes)
 /// suitible for glob-importing.
 pub mod prelude {
     #[doc(no_inline)]
-    pub use ::{AndThenExt, ComposeExt, Identity, InvertExt, Iso, Lens, PartialLens, Prism};
+    pub use ::{AndThenExt, ComposeExt, Fold, Identity, InvertExt, Iso, Lens, LensMut, LensRef,
+               PartialLens, Prism, Traversal};
+    #[doc(no_inline)]
+    pub use ::access::{Getter, Review, Setter};
 }
 
 /// Some utility functions used inside this crate, but possibly useful for
 /// others as well.
 pub mod util {
-    pub fn once_to_mut<'a, X, Y, F>(f_once: F) -> Box<FnMut(X) -> Option<Y> + 'a>
+    use std::fmt;
+    use std::marker::PhantomData;
+    use std::mem::{ManuallyDrop, MaybeUninit};
+    use std::ptr;
+
+    pub fn once_to_mut<'a, X, Y, F>(f_once: F) -> Box<dyn FnMut(X) -> Option<Y> + 'a>
         where F: FnOnce(X) -> Y + 'a
     {
         let mut f_opt: Option<F> = Some(f_once);
         Box::new(move |x| f_opt.take().map(move |f| f(x)))
     }
 
-    pub fn once_to_mut_flatten<'a, X, Y, F>(f_once: F) -> Box<FnMut(X) -> Option<Y> + 'a>
+    pub fn once_to_mut_flatten<'a, X, Y, F>(f_once: F) -> Box<dyn FnMut(X) -> Option<Y> + 'a>
         where F: FnOnce(X) -> Option<Y> + 'a
     {
         let mut f_opt: Option<F> = Some(f_once);
         Box::new(move |x| f_opt.take().and_then(move |f| f(x)))
     }
+
+    /// A value with one of its fields moved out, as left behind by
+    /// `field_lens!`.  A `Hole` that is dropped without being filled, whether
+    /// discarded or during unwinding, drops the fields that remain.
+    ///
+    /// The lens `L` that made the hole tags its type, so that a hole can't be
+    /// filled through a different field of the same source:
+    ///
+    /// ```compile_fail
+    /// # #[macro_use] extern crate refraction;
+    /// # use refraction::PartialLens;
+    /// # fn main() {
+    /// let first = field_lens!((String, String) => 0: String);
+    /// let second = field_lens!((String, String) => 1: String);
+    /// let (_, c) = first.try_get_context((String::new(), String::new())).ok().unwrap();
+    /// second.fill_context(c, String::new());
+    /// # }
+    /// ```
+    pub struct Hole<S, L> {
+        source: MaybeUninit<S>,
+        drop_rest: fn(S),
+        phantom_l: PhantomData<L>,
+    }
+
+    impl<S, L> Hole<S, L> {
+        /// Move a field out of `v`, given a projection from `v` to that field
+        /// and a function that drops a value of the same type but for that
+        /// field (typically by moving the field out and forgetting it).
+        ///
+        /// # Safety
+        ///
+        /// `field` must project onto a field of the value it is given, the
+        /// resulting `Hole` must only ever be filled consistently with that
+        /// field having been moved out, and `drop_rest` must not drop that
+        /// field.  Every `Hole` tagged with `L` must be taken through the same
+        /// field.
+        #[inline]
+        pub unsafe fn take<A, F>(v: S, field: F, drop_rest: fn(S)) -> (A, Self)
+            where F: FnOnce(*const S) -> *const A
+        {
+            let source = MaybeUninit::new(v);
+            let x = ptr::read(field(source.as_ptr()));
+            (x,
+             Hole { source,
+                    drop_rest,
+                    phantom_l: PhantomData, })
+        }
+
+        /// Put a new value where the field was moved out from, recovering the
+        /// whole value.
+        ///
+        /// # Safety
+        ///
+        /// `field` must project onto the same field that was moved out.
+        #[inline]
+        pub unsafe fn fill<A, F>(self, field: F, x: A) -> S
+            where F: FnOnce(*mut S) -> *mut A
+        {
+            let mut this = ManuallyDrop::new(self);
+            ptr::write(field(this.source.as_mut_ptr()), x);
+            this.source.assume_init_read()
+        }
+    }
+
+    impl<S, L> Drop for Hole<S, L> {
+        fn drop(&mut self) {
+            // the source is initialized but for the moved-out field, which
+            // `drop_rest` leaves alone
+            (self.drop_rest)(unsafe { self.source.assume_init_read() })
+        }
+    }
+
+    impl<S, L> fmt::Debug for Hole<S, L> {
+        fn fmt(&self, fm: &mut fmt::Formatter) -> fmt::Result {
+            fm.debug_struct("Hole").finish()
+        }
+    }
 }
 
 /// The supertype of all lenticuloids.
@@ -76,8 +177,8 @@ pub trait Lenticuloid {
 
 /// The identity lenticuloid.
 pub struct Identity<S, T> {
-    phantom_ss: PhantomData<Fn(S) -> S>,
-    phantom_tt: PhantomData<Fn(T) -> T>,
+    phantom_ss: PhantomData<fn(S) -> S>,
+    phantom_tt: PhantomData<fn(T) -> T>,
 }
 
 impl<S, T> fmt::Debug for Identity<S, T> {
@@ -222,6 +323,12 @@ impl<L: Lenticuloid + ?Sized> Lenticuloid for Invert<L> {
     }
 }
 
+mod fold;
+pub use fold::*;
+
+mod traversal;
+pub use traversal::*;
+
 mod partial_lens;
 pub use partial_lens::*;
 
@@ -249,3 +356,13 @@ pub mod conv;
 pub mod errors;
 
 pub mod collections;
+
+pub mod func;
+
+pub mod erased;
+
+#[macro_use]
+pub mod dynamic;
+
+#[cfg(feature = "serde_json")]
+pub mod json;
@@ -65,6 +65,104 @@ macro_rules! chain_and_then {
     ($lf:expr, $($ls:expr),+) => { $crate::Compose::of(chain_and_then!($($ls),+), $lf) };
 }
 
+/// Implement `Fold`, `Setter` and `Traversal` for a partial lens in terms of
+/// its `PartialLens` implementation, which focuses at most one target.
+///
+/// `Traversal` (and through it `Fold` and `Setter`) is a supertrait of
+/// `PartialLens`, so every `PartialLens` implementation needs these as well;
+/// this is the intended way to provide them:
+///
+/// ```ignore
+/// impl_traversal_via_partial_lens!([A: Clone] MyPrism<A>);
+/// impl_traversal_via_partial_lens!([S, A] MyLens<S, A> where [S: AsRef<A>]);
+/// ```
+#[macro_export]
+macro_rules! impl_traversal_via_partial_lens {
+    ([$($generics:tt)*] $lens:ty where [$($bounds:tt)*]) => {
+        impl<$($generics)*> $crate::Fold for $lens
+            where $($bounds)*
+        {
+            #[inline]
+            fn for_each<__F>(&self, v: Self::InitialSource, mut f: __F)
+                where __F: FnMut(Self::InitialTarget)
+            {
+                if let ::std::result::Result::Ok(x) = $crate::PartialLens::try_get(self, v) {
+                    f(x)
+                }
+            }
+        }
+
+        impl<$($generics)*> $crate::access::Setter for $lens
+            where $($bounds)*
+        {
+            #[inline]
+            fn over<__F>(&self, v: Self::InitialSource, f: __F) -> Self::FinalSource
+                where __F: FnMut(Self::InitialTarget) -> Self::FinalTarget
+            {
+                $crate::PartialLens::modify(self, v, f)
+            }
+        }
+
+        impl<$($generics)*> $crate::Traversal for $lens
+            where $($bounds)*
+        {
+        }
+    };
+    ([$($generics:tt)*] $lens:ty) => {
+        $crate::impl_traversal_via_partial_lens!([$($generics)*] $lens where []);
+    };
+}
+
+/// Implement `Getter` for a lens in terms of its `Lens` impl.
+///
+/// `Getter` is a supertrait of `Lens`, so every `Lens` implementation needs it
+/// as well; this is the intended way to provide it:
+///
+/// ```ignore
+/// impl_getter_via_lens!([S, A] MyLens<S, A> where [S: AsRef<A>]);
+/// ```
+#[macro_export]
+macro_rules! impl_getter_via_lens {
+    ([$($generics:tt)*] $lens:ty where [$($bounds:tt)*]) => {
+        impl<$($generics)*> $crate::access::Getter for $lens
+            where $($bounds)*
+        {
+            #[inline]
+            fn view(&self, v: Self::InitialSource) -> Self::InitialTarget {
+                $crate::Lens::get(self, v)
+            }
+        }
+    };
+    ([$($generics:tt)*] $lens:ty) => {
+        $crate::impl_getter_via_lens!([$($generics)*] $lens where []);
+    };
+}
+
+/// Implement `Review` for a prism in terms of its `Prism` impl.
+///
+/// `Review` is a supertrait of `Prism`, so every `Prism` implementation needs
+/// it as well; this is the intended way to provide it:
+///
+/// ```ignore
+/// impl_review_via_prism!([A: Clone] MyPrism<A>);
+/// ```
+#[macro_export]
+macro_rules! impl_review_via_prism {
+    ([$($generics:tt)*] $prism:ty where [$($bounds:tt)*]) => {
+        impl<$($generics)*> $crate::access::Review for $prism
+            where $($bounds)*
+        {
+            #[inline]
+            fn review(&self, v: Self::FinalTarget) -> Self::FinalSource {
+                $crate::Prism::inject(self, v)
+            }
+        }
+    };
+    ([$($generics:tt)*] $prism:ty) => {
+        $crate::impl_review_via_prism!([$($generics)*] $prism where []);
+    };
+}
+
 /// Extension `trait` for lenticuloid inversion.
 pub trait InvertExt: Lenticuloid + Sized {
     fn invert(self) -> Invert<Self>;
@@ -79,107 +177,229 @@ impl<This: Lenticuloid> InvertExt for This {
 
 /// Create a simple lens inline to address a specific (possibly nested) field
 /// of a type.
+///
+/// A single field may also be addressed in a type-changing way, by giving both
+/// the initial and final types of the source and of the field.  The struct is
+/// then rebuilt at its final type, so any other fields it has must be listed,
+/// with their types, after a `;`:
+///
+/// ```ignore
+/// field_lens!(Wrapper<u32> => Wrapper<String>, payload: u32 => String; count: usize)
+/// ```
 #[macro_export]
 macro_rules! field_lens {
-    ($source:ty => $($field_name:tt).*: $target:ty) => {
+    (@define $name:ident, $source:ty => $($field_name:tt).*: $target:ty) => {
+        #[derive(Copy,Clone,Debug,Default)]
+        struct $name;
+        impl $crate::Lenticuloid for $name {
+            type InitialSource = $source;
+            type InitialTarget = $target;
+            type FinalSource = $source;
+            type FinalTarget = $target;
+            type AtInitial = Self;
+            #[inline]
+            fn at_initial(&self) -> Self::AtInitial {
+                *self
+            }
+            type AtFinal = Self;
+            #[inline]
+            fn at_final(&self) -> Self::AtFinal {
+                *self
+            }
+        }
+        $crate::impl_traversal_via_partial_lens!([] $name);
+        impl $crate::PartialLens for $name {
+            type Context = $crate::util::Hole<$source, $name>;
+            #[inline]
+            fn try_get(&self, v: Self::InitialSource) ->
+                ::std::result::Result<Self::InitialTarget, Self::FinalSource>
+            {
+                ::std::result::Result::Ok(v$(.$field_name)*)
+            }
+            #[inline]
+            fn try_get_context(&self, v: Self::InitialSource) ->
+                ::std::result::Result<(Self::InitialTarget, Self::Context),
+                                            Self::FinalSource>
+            {
+                // the projection is onto a field of `v`, the hole is tagged
+                // with this lens and so only ever filled through that same
+                // field, and dropping the rest of the value forgets that field
+                let drop_rest = |v: $source| {
+                    let _ = ::std::mem::ManuallyDrop::new(v$(.$field_name)*);
+                };
+                ::std::result::Result::Ok(unsafe {
+                    $crate::util::Hole::take(v,
+                                             |p| ::std::ptr::addr_of!((*p)$(.$field_name)*),
+                                             drop_rest)
+                })
+            }
+            #[inline]
+            fn fill_context(&self, c: Self::Context, x: Self::FinalTarget) ->
+                Self::FinalSource
+            {
+                unsafe {
+                    c.fill(|p| ::std::ptr::addr_of_mut!((*p)$(.$field_name)*), x)
+                }
+            }
+            #[inline]
+            fn set(&self, mut v: Self::InitialSource, x: Self::FinalTarget) ->
+                Self::FinalSource
+            {
+                v$(.$field_name)* = x;
+                v
+            }
+            #[inline]
+            fn exchange(&self,
+                        mut v: Self::InitialSource,
+                        mut x: Self::FinalTarget) ->
+                (::std::option::Option<Self::InitialTarget>,
+                 Self::FinalSource)
+            {
+                ::std::mem::swap(&mut v$(.$field_name)*, &mut x);
+                (::std::option::Option::Some(x), v)
+            }
+            #[inline]
+            fn modify<F>(&self, mut v: Self::InitialSource, f: F) -> Self::FinalSource
+                where F: FnOnce(Self::InitialTarget) -> Self::FinalTarget
+            {
+                v$(.$field_name)* = f(v$(.$field_name)*);
+                v
+            }
+            #[inline]
+            fn modify_with<F, X>(&self, mut v: Self::InitialSource, f: F) ->
+                (Self::FinalSource, ::std::option::Option<X>)
+                where F: FnOnce(Self::InitialTarget) -> (Self::FinalTarget, X)
+            {
+                let (x, aux) = f(v$(.$field_name)*);
+                v$(.$field_name)* = x;
+                (v, ::std::option::Option::Some(aux))
+            }
+        }
+        $crate::impl_getter_via_lens!([] $name);
+        impl $crate::Lens for $name {
+            #[inline]
+            fn get(&self, v: Self::InitialSource) -> Self::InitialTarget
+            {
+                v$(.$field_name)*
+            }
+        }
+        impl $crate::LensRef for $name {
+            #[inline]
+            fn view_ref<'a>(&self, v: &'a Self::InitialSource) -> &'a Self::InitialTarget
+                where Self: 'a
+            {
+                &v$(.$field_name)*
+            }
+        }
+        impl $crate::LensMut for $name {
+            #[inline]
+            fn view_mut<'a>(&self, v: &'a mut Self::InitialSource) ->
+                &'a mut Self::InitialTarget
+                where Self: 'a
+            {
+                &mut v$(.$field_name)*
+            }
+        }
+    };
+    ($source:ty => $final_source:ty,
+     $field_name:ident: $target:ty => $final_target:ty $(; $($rest:ident: $rest_ty:ty),*)*) => {
         {
+            type __Initial__ = $source;
+            type __Final__ = $final_source;
+            field_lens!(@define __FieldLensInitial__, $source => $field_name: $target);
+            field_lens!(@define __FieldLensFinal__, $final_source => $field_name: $final_target);
             #[derive(Copy,Clone,Debug,Default)]
             struct __FieldLens__;
             impl $crate::Lenticuloid for __FieldLens__ {
                 type InitialSource = $source;
                 type InitialTarget = $target;
-                type FinalSource = $source;
-                type FinalTarget = $target;
-                type AtInitial = Self;
+                type FinalSource = $final_source;
+                type FinalTarget = $final_target;
+                type AtInitial = __FieldLensInitial__;
                 #[inline]
                 fn at_initial(&self) -> Self::AtInitial {
-                    *self
+                    __FieldLensInitial__
                 }
-                type AtFinal = Self;
+                type AtFinal = __FieldLensFinal__;
                 #[inline]
                 fn at_final(&self) -> Self::AtFinal {
-                    *self
+                    __FieldLensFinal__
                 }
             }
+            $crate::impl_traversal_via_partial_lens!([] __FieldLens__);
             impl $crate::PartialLens for __FieldLens__ {
+                type Context = ($($($rest_ty,)*)*);
                 #[inline]
                 fn try_get(&self, v: Self::InitialSource) ->
-                    $crate::std::result::Result<Self::InitialTarget, Self::FinalSource>
+                    ::std::result::Result<Self::InitialTarget, Self::FinalSource>
                 {
-                    $crate::std::result::Result::Ok(v$(.$field_name)*)
+                    ::std::result::Result::Ok(v.$field_name)
                 }
                 #[inline]
-                fn try_get_inject(&self, mut v: Self::InitialSource) ->
-                    $crate::std::result::Result<(Self::InitialTarget,
-                                                 $crate::Injector<Self::FinalTarget,
-                                                 Self::FinalSource>), Self::FinalSource>
+                fn try_get_context(&self, v: Self::InitialSource) ->
+                    ::std::result::Result<(Self::InitialTarget, Self::Context),
+                                                Self::FinalSource>
                 {
-                    // this is safe because we fully own `v` and can NoDrop-wrap it
-                    let x = $crate::std::mem::replace(&mut v$(.$field_name)*, unsafe {
-                        $crate::std::mem::uninitialized()
-                    });
-                    let v_no_drop = $crate::nodrop::NoDrop::new(v);
-                    $crate::std::result::Result::Ok((
-                        x,
-                        $crate::util::once_to_mut(move |y| {
-                            let mut v_final = v_no_drop.into_inner();
-                            $crate::std::mem::forget($crate::std::mem::replace(
-                                &mut v_final$(.$field_name)*,
-                                y
-                            ));
-                            v_final
-                        })
-                    ))
+                    let __Initial__ { $field_name: x, $($($rest),*)* } = v;
+                    ::std::result::Result::Ok((x, ($($($rest,)*)*)))
                 }
                 #[inline]
-                fn set(&self, mut v: Self::InitialSource, x: Self::FinalTarget) ->
+                fn fill_context(&self, c: Self::Context, y: Self::FinalTarget) ->
                     Self::FinalSource
                 {
-                    v$(.$field_name)* = x;
-                    v
+                    let ($($($rest,)*)*) = c;
+                    __Final__ { $field_name: y, $($($rest),*)* }
                 }
                 #[inline]
-                fn exchange(&self,
-                            mut v: Self::InitialSource,
-                            mut x: Self::FinalTarget) ->
-                    ($crate::std::option::Option<Self::InitialTarget>,
-                     Self::FinalSource)
+                fn set(&self, v: Self::InitialSource, x: Self::FinalTarget) ->
+                    Self::FinalSource
                 {
-                    $crate::std::mem::swap(&mut v$(.$field_name)*, &mut x);
-                    ($crate::std::option::Option::Some(x), v)
+                    let __Initial__ { $field_name: _, $($($rest),*)* } = v;
+                    __Final__ { $field_name: x, $($($rest),*)* }
                 }
                 #[inline]
-                fn modify<F>(&self, mut v: Self::InitialSource, f: F) -> Self::FinalSource
+                fn modify<F>(&self, v: Self::InitialSource, f: F) -> Self::FinalSource
                     where F: FnOnce(Self::InitialTarget) -> Self::FinalTarget
                 {
-                    v$(.$field_name)* = f(v$(.$field_name)*);
-                    v
+                    let __Initial__ { $field_name: x, $($($rest),*)* } = v;
+                    __Final__ { $field_name: f(x), $($($rest),*)* }
                 }
                 #[inline]
-                fn modify_with<F, X>(&self, mut v: Self::InitialSource, f: F) ->
-                    (Self::FinalSource, $crate::std::option::Option<X>)
+                fn modify_with<F, X>(&self, v: Self::InitialSource, f: F) ->
+                    (Self::FinalSource, ::std::option::Option<X>)
                     where F: FnOnce(Self::InitialTarget) -> (Self::FinalTarget, X)
                 {
-                    let (x, aux) = f(v$(.$field_name)*);
-                    v$(.$field_name)* = x;
-                    (v, $crate::std::option::Option::Some(aux))
+                    let __Initial__ { $field_name: x, $($($rest),*)* } = v;
+                    let (y, aux) = f(x);
+                    (__Final__ { $field_name: y, $($($rest),*)* },
+                     ::std::option::Option::Some(aux))
                 }
             }
+            $crate::impl_getter_via_lens!([] __FieldLens__);
             impl $crate::Lens for __FieldLens__ {
                 #[inline]
                 fn get(&self, v: Self::InitialSource) -> Self::InitialTarget
                 {
-                    v$(.$field_name)*
+                    v.$field_name
                 }
             }
             __FieldLens__
         }
-    }
+    };
+    ($source:ty => $($field_name:tt).*: $target:ty) => {
+        {
+            field_lens!(@define __FieldLens__, $source => $($field_name).*: $target);
+            __FieldLens__
+        }
+    };
 }
 
 #[cfg(test)]
 mod test {
-    use ::PartialLens;
+    use std::panic::{self, AssertUnwindSafe};
+    use std::rc::Rc;
+    use ::{ComposeExt, LensMut, LensRef, Lenticuloid, PartialLens};
+    use collections::Index;
     #[test]
     fn test_field_lens() {
         struct TestInner(String);
@@ -191,4 +411,154 @@ mod test {
         let w = l.modify(v, |x| x.trim().to_string());
         assert_eq!(w.test_field.0, "hello")
     }
+
+    #[test]
+    fn test_type_changing_field_lens() {
+        #[derive(Debug, PartialEq)]
+        struct Wrapper<A> {
+            payload: A,
+            count: usize,
+        }
+        let l = field_lens!(Wrapper<u32> => Wrapper<String>, payload: u32 => String; count: usize);
+        let w = l.modify(Wrapper { payload: 7, count: 1 }, |x| x.to_string());
+        assert_eq!(w, Wrapper { payload: "7".to_string(), count: 1 });
+        let (x, mut inj) = l.try_get_inject(Wrapper { payload: 8, count: 2 }).unwrap();
+        assert_eq!(x, 8);
+        assert_eq!(inj("eight".to_string()), Some(Wrapper { payload: "eight".to_string(), count: 2 }));
+        assert_eq!(l.at_final().set(w, "x".to_string()).payload, "x");
+    }
+
+    #[test]
+    fn test_field_lens_inject() {
+        #[derive(Debug)]
+        struct Inner(bool, &'static str);
+        #[derive(Debug)]
+        struct Outer {
+            inner: Inner,
+            name: String,
+        }
+        let l = field_lens!(Outer => inner.1: &'static str);
+        let v = Outer { inner: Inner(true, "old"), name: "outer".to_string() };
+        let (x, mut inj) = l.try_get_inject(v).unwrap();
+        assert_eq!(x, "old");
+        let w = inj("new").unwrap();
+        assert!(w.inner.0);
+        assert_eq!(w.inner.1, "new");
+        assert_eq!(w.name, "outer");
+        assert!(inj("newer").is_none());
+        let l = field_lens!(Outer => inner.0: bool);
+        let w = l.modify(w, |b| !b);
+        assert!(!w.inner.0);
+    }
+
+    #[test]
+    fn test_field_lens_inject_unwind() {
+        #[derive(Debug)]
+        struct Pair {
+            first: Rc<()>,
+            second: Rc<()>,
+        }
+        let (first, second) = (Rc::new(()), Rc::new(()));
+        let l = field_lens!(Pair => first: Rc<()>);
+        let res = panic::catch_unwind(AssertUnwindSafe(|| {
+            let v = Pair { first: first.clone(), second: second.clone() };
+            let (_x, _inj) = l.try_get_inject(v).unwrap();
+            panic!("unwinding with the injector still live");
+        }));
+        assert!(res.is_err());
+        // the moved-out field and the rest are each dropped exactly once
+        assert_eq!(Rc::strong_count(&first), 1);
+        assert_eq!(Rc::strong_count(&second), 1);
+
+        let v = Pair { first: first.clone(), second: second.clone() };
+        let (x, mut inj) = l.try_get_inject(v).unwrap();
+        drop(x);
+        let w = inj(Rc::new(())).unwrap();
+        assert_eq!(Rc::strong_count(&first), 1);
+        assert!(Rc::ptr_eq(&w.second, &second));
+        drop(w);
+        assert_eq!(Rc::strong_count(&second), 1);
+    }
+
+    #[test]
+    fn test_field_lens_modify_unwind() {
+        struct Pair<A> {
+            first: A,
+            second: Rc<()>,
+        }
+        let (first, second) = (Rc::new(()), Rc::new(()));
+        let pair = || Pair { first: first.clone(), second: second.clone() };
+        let l = field_lens!(Pair<Rc<()>> => first: Rc<()>);
+        let res = panic::catch_unwind(AssertUnwindSafe(|| {
+            l.modify(pair(), |x| {
+                drop(x);
+                panic!("unwinding from inside modify")
+            })
+        }));
+        assert!(res.is_err());
+        // the field taken by the closure and the rest are each dropped once
+        assert_eq!(Rc::strong_count(&first), 1);
+        assert_eq!(Rc::strong_count(&second), 1);
+        let res = panic::catch_unwind(AssertUnwindSafe(|| {
+            l.modify_with(pair(), |x| -> (Rc<()>, ()) {
+                drop(x);
+                panic!("unwinding from inside modify_with")
+            })
+        }));
+        assert!(res.is_err());
+        assert_eq!(Rc::strong_count(&first), 1);
+        assert_eq!(Rc::strong_count(&second), 1);
+
+        let l = field_lens!(Pair<Rc<()>> => Pair<String>, first: Rc<()> => String; second: Rc<()>);
+        let res = panic::catch_unwind(AssertUnwindSafe(|| {
+            l.modify_with(pair(), |x| -> (String, ()) {
+                drop(x);
+                panic!("unwinding from inside a type-changing modify_with")
+            })
+        }));
+        assert!(res.is_err());
+        assert_eq!(Rc::strong_count(&first), 1);
+        assert_eq!(Rc::strong_count(&second), 1);
+        let res = panic::catch_unwind(AssertUnwindSafe(|| {
+            l.modify(pair(), |_| panic!("unwinding from inside a type-changing modify"))
+        }));
+        assert!(res.is_err());
+        assert_eq!(Rc::strong_count(&first), 1);
+        assert_eq!(Rc::strong_count(&second), 1);
+    }
+
+    #[test]
+    fn test_field_lens_discarded_context() {
+        struct Outer {
+            items: Vec<u32>,
+            name: Rc<()>,
+        }
+        let name = Rc::new(());
+        let l = Index::of(0).compose(field_lens!(Outer => items: Vec<u32>));
+        for _ in 0..3 {
+            let v = Outer { items: vec![1, 2], name: name.clone() };
+            assert_eq!(l.try_get(v).ok(), Some(1));
+        }
+        assert_eq!(Rc::strong_count(&name), 1);
+        let v = Outer { items: vec![], name: name.clone() };
+        let w = l.try_get(v).err().unwrap();
+        assert!(Rc::ptr_eq(&w.name, &name));
+        drop(w);
+        assert_eq!(Rc::strong_count(&name), 1);
+    }
+
+    #[test]
+    fn test_field_lens_by_ref() {
+        struct Inner {
+            count: u32,
+        }
+        struct Outer {
+            inner: Inner,
+        }
+        let l = field_lens!(Inner => count: u32).compose(field_lens!(Outer => inner: Inner));
+        let mut v = Outer { inner: Inner { count: 1 } };
+        *l.view_mut(&mut v) += 2;
+        assert_eq!(*l.view_ref(&v), 3);
+        assert_eq!(*field_lens!(Outer => inner.count: u32).view_ref(&v), 3);
+    }
 }
@@ -1,25 +1,48 @@
-use super::{Compose, Identity, Invert, Iso, Lenticuloid, util};
+use super::{Compose, Identity, Invert, Iso, Traversal, util};
 
-pub type Injector<'l, X, Y> = Box<FnMut(X) -> Option<Y> + 'l>;
+pub type Injector<'l, X, Y> = Box<dyn FnMut(X) -> Option<Y> + 'l>;
 
 /// The supertype of all partial lens families.
-pub trait PartialLens: Lenticuloid
+///
+/// `Traversal`, and through it `Fold` and `Setter`, are required supertraits:
+/// existing implementations must now provide them too, which
+/// [`impl_traversal_via_partial_lens!`](::impl_traversal_via_partial_lens!)
+/// does in terms of this trait.
+pub trait PartialLens: Traversal
     where Self::AtInitial: PartialLens,
           Self::AtFinal: PartialLens
 {
+    /// Whatever is left of a source once its target has been taken out; it is
+    /// passed by value, so going through it never allocates.
+    type Context;
+
     fn try_get(&self, v: Self::InitialSource) -> Result<Self::InitialTarget, Self::FinalSource> {
-        self.try_get_inject(v).map(|(x, _)| x)
+        self.try_get_context(v).map(|(x, _)| x)
     }
 
-    /// This signature is somewhat hacky; it awaits resolution of the `FnBox`
-    /// issue for better design. Notably, the injection function returned by
-    /// this method will (if law-abiding) only return `Some` exactly once;
-    /// every time afterwards, it will return `None`.
+    /// Take the target out of a source, keeping the rest of the source as a
+    /// context from which `fill_context` can rebuild it.
+    fn try_get_context(&self,
+                       v: Self::InitialSource)
+                       -> Result<(Self::InitialTarget, Self::Context), Self::FinalSource>;
+
+    /// Rebuild a source from a context left by `try_get_context` and a new
+    /// target.
+    fn fill_context(&self, c: Self::Context, x: Self::FinalTarget) -> Self::FinalSource;
+
+    /// Take the target out of a source along with a function to put a new
+    /// one back.  The function will (if law-abiding) only return `Some`
+    /// exactly once; every time afterwards, it will return `None`.  By
+    /// default it boxes up a context from `try_get_context` to fill with
+    /// `fill_context`, so those should be preferred where possible.
     fn try_get_inject(&self,
                       v: Self::InitialSource)
                       -> Result<(Self::InitialTarget,
-                                 Injector<Self::FinalTarget, Self::FinalSource>),
-                                Self::FinalSource>;
+                                 Injector<'_, Self::FinalTarget, Self::FinalSource>),
+                                Self::FinalSource> {
+        self.try_get_context(v)
+            .map(move |(x, c)| (x, util::once_to_mut(move |y| self.fill_context(c, y))))
+    }
 
     fn set(&self, v: Self::InitialSource, x: Self::FinalTarget) -> Self::FinalSource {
         self.modify(v, |_| x)
@@ -44,14 +67,21 @@ pub trait PartialLens: Lenticuloid
 }
 
 impl<S, T> PartialLens for Identity<S, T> {
+    type Context = ();
+
     #[inline]
     fn try_get(&self, v: S) -> Result<S, T> {
         Ok(v)
     }
 
     #[inline]
-    fn try_get_inject(&self, v: S) -> Result<(S, Injector<T, T>), T> {
-        Ok((v, util::once_to_mut(|x| x)))
+    fn try_get_context(&self, v: S) -> Result<(S, ()), T> {
+        Ok((v, ()))
+    }
+
+    #[inline]
+    fn fill_context(&self, _c: (), x: T) -> T {
+        x
     }
 
     #[inline]
@@ -83,33 +113,26 @@ impl<LF: PartialLens, LS: ?Sized> PartialLens for Compose<LF, LS>
           LS::AtInitial: PartialLens,
           LS::AtFinal: PartialLens
 {
+    type Context = (LS::Context, LF::Context);
+
     fn try_get(&self, v: Self::InitialSource) -> Result<Self::InitialTarget, Self::FinalSource> {
-        let Compose { first: ref lf, second: ref ls } = *self;
-        ls.try_get_inject(v).and_then(move |(q, mut inj)| {
-            lf.try_get(q).map_err(move |x| inj(x).unwrap_or_else(|| unreachable!()))
-        })
+        let (q, cs) = self.second.try_get_context(v)?;
+        self.first.try_get(q).map_err(|q| self.second.fill_context(cs, q))
     }
 
-    fn try_get_inject(&self,
-                      v: Self::InitialSource)
-                      -> Result<(Self::InitialTarget,
-                                 Injector<Self::FinalTarget, Self::FinalSource>),
-                                Self::FinalSource> {
-        let Compose { first: ref lf, second: ref ls } = *self;
-        ls.try_get_inject(v).and_then(move |(q, mut inj_q)| {
-            let res = lf.try_get_inject(q).map(|(x, mut inj_x)| {
-                (x, move |y| inj_x(y).unwrap_or_else(|| unreachable!()))
-            });
-            match res {
-                Ok((x, mut inj_x)) => {
-                    Ok((x,
-                        util::once_to_mut(move |y| {
-                            inj_q(inj_x(y)).unwrap_or_else(|| unreachable!())
-                        })))
-                }
-                Err(q) => Err(inj_q(q).unwrap_or_else(|| unreachable!())),
-            }
-        })
+    fn try_get_context(&self,
+                       v: Self::InitialSource)
+                       -> Result<(Self::InitialTarget, Self::Context), Self::FinalSource> {
+        let (q, cs) = self.second.try_get_context(v)?;
+        match self.first.try_get_context(q) {
+            Ok((x, cf)) => Ok((x, (cs, cf))),
+            Err(q) => Err(self.second.fill_context(cs, q)),
+        }
+    }
+
+    fn fill_context(&self, c: Self::Context, x: Self::FinalTarget) -> Self::FinalSource {
+        let (cs, cf) = c;
+        self.second.fill_context(cs, self.first.fill_context(cf, x))
     }
 
     fn set(&self, v: Self::InitialSource, x: Self::FinalTarget) -> Self::FinalSource {
@@ -145,18 +168,23 @@ impl<L: Iso> PartialLens for Invert<L>
     where L::AtInitial: Iso,
           L::AtFinal: Iso
 {
+    type Context = ();
+
     #[inline]
     fn try_get(&self, v: Self::InitialSource) -> Result<Self::InitialTarget, Self::FinalSource> {
         Ok(self.deinvert.inject(v))
     }
 
     #[inline]
-    fn try_get_inject(&self,
-                      v: Self::InitialSource)
-                      -> Result<(Self::InitialTarget,
-                                 Injector<Self::FinalTarget, Self::FinalSource>),
-                                Self::FinalSource> {
-        Ok((self.deinvert.inject(v), util::once_to_mut(move |x| self.deinvert.get(x))))
+    fn try_get_context(&self,
+                       v: Self::InitialSource)
+                       -> Result<(Self::InitialTarget, ()), Self::FinalSource> {
+        Ok((self.deinvert.inject(v), ()))
+    }
+
+    #[inline]
+    fn fill_context(&self, _c: (), x: Self::FinalTarget) -> Self::FinalSource {
+        self.deinvert.get(x)
     }
 
     #[inline]
@@ -190,3 +218,23 @@ impl<L: Iso> PartialLens for Invert<L>
         (l.get(x), Some(ret))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::ComposeExt;
+    use collections::Index;
+
+    #[test]
+    fn test_compose_context() {
+        let l = Index::<Vec<u32>>::of(1).compose(Index::<Vec<Vec<u32>>>::of(0));
+        let (x, c) = l.try_get_context(vec![vec![1, 2, 3]]).unwrap();
+        assert_eq!(x, 2);
+        assert_eq!(l.fill_context(c, 20), vec![vec![1, 20, 3]]);
+        assert_eq!(l.try_get_context(vec![vec![1]]).err(), Some(vec![vec![1]]));
+        let (x, mut inj) = l.try_get_inject(vec![vec![4, 5]]).unwrap();
+        assert_eq!(x, 5);
+        assert_eq!(inj(50), Some(vec![vec![4, 50]]));
+        assert_eq!(inj(60), None);
+    }
+}
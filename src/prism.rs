@@ -1,11 +1,24 @@
+use super::access::{AsReview, Review};
 use super::{Compose, Identity, Invert, Iso, PartialLens};
 
 /// The supertype of all prism families.
-pub trait Prism: PartialLens
+///
+/// `Review` is a required supertrait, which
+/// [`impl_review_via_prism!`](::impl_review_via_prism!) provides in terms of
+/// this trait.
+pub trait Prism: PartialLens + Review
     where Self::AtInitial: Prism,
           Self::AtFinal: Prism
 {
     fn inject(&self, v: Self::FinalTarget) -> Self::FinalSource;
+
+    /// Forget this prism's ability to match, leaving only a `Review`.
+    #[allow(clippy::wrong_self_convention)]
+    fn as_review(self) -> AsReview<Self>
+        where Self: Sized
+    {
+        AsReview::of(self)
+    }
 }
 
 impl<S, T> Prism for Identity<S, T> {
@@ -4,13 +4,13 @@
 
 use std::fmt::{self, Debug, Formatter};
 use std::marker::PhantomData;
-use super::{Injector, Lens, Lenticuloid, PartialLens, Prism, util};
+use super::{Lens, Lenticuloid, PartialLens, Prism};
 
 #[cfg(feature = "nightly")]
 /// A `Lens` to "extract" anything from `!`.
 pub struct FromNever<A, B> {
-    phantom_na: PhantomData<Fn(!) -> A>,
-    phantom_bn: PhantomData<Fn(B) -> !>,
+    phantom_na: PhantomData<fn(!) -> A>,
+    phantom_bn: PhantomData<fn(B) -> !>,
 }
 
 #[cfg(feature = "nightly")]
@@ -75,17 +75,26 @@ impl<A, B> Lenticuloid for FromNever<A, B> {
     }
 }
 
+#[cfg(feature = "nightly")]
+impl_traversal_via_partial_lens!([A, B] FromNever<A, B>);
+
 #[cfg(feature = "nightly")]
 #[allow(unreachable_code)]
 impl<A, B> PartialLens for FromNever<A, B> {
+    type Context = !;
+
     fn try_get(&self, v: !) -> Result<A, !> {
         v
     }
 
-    fn try_get_inject(&self, v: !) -> Result<(A, Injector<B, !>), !> {
+    fn try_get_context(&self, v: !) -> Result<(A, !), !> {
         v
     }
 
+    fn fill_context(&self, c: !, _x: B) -> ! {
+        c
+    }
+
     fn set(&self, v: !, _x: B) -> ! {
         v
     }
@@ -105,6 +114,9 @@ impl<A, B> PartialLens for FromNever<A, B> {
     }
 }
 
+#[cfg(feature = "nightly")]
+impl_getter_via_lens!([A, B] FromNever<A, B>);
+
 #[cfg(feature = "nightly")]
 #[allow(unreachable_code)]
 impl<A, B> Lens for FromNever<A, B> {
@@ -116,8 +128,8 @@ impl<A, B> Lens for FromNever<A, B> {
 #[cfg(feature = "nightly")]
 /// A `Prism` to "inject" `!` into anything.
 pub struct ToNever<S> {
-    phantom_ns: PhantomData<Fn(!) -> S>,
-    phantom_sn: PhantomData<Fn(S) -> !>,
+    phantom_ns: PhantomData<fn(!) -> S>,
+    phantom_sn: PhantomData<fn(S) -> !>,
 }
 
 #[cfg(feature = "nightly")]
@@ -182,17 +194,26 @@ impl<S> Lenticuloid for ToNever<S> {
     }
 }
 
+#[cfg(feature = "nightly")]
+impl_traversal_via_partial_lens!([S] ToNever<S>);
+
 #[cfg(feature = "nightly")]
 #[allow(unreachable_code)]
 impl<S> PartialLens for ToNever<S> {
+    type Context = ();
+
     fn try_get(&self, v: S) -> Result<!, S> {
         Err(v)
     }
 
-    fn try_get_inject(&self, v: S) -> Result<(!, Injector<!, S>), S> {
+    fn try_get_context(&self, v: S) -> Result<(!, ()), S> {
         Err(v)
     }
 
+    fn fill_context(&self, _c: (), x: !) -> S {
+        x
+    }
+
     fn set(&self, _v: S, x: !) -> S {
         x
     }
@@ -214,6 +235,9 @@ impl<S> PartialLens for ToNever<S> {
     }
 }
 
+#[cfg(feature = "nightly")]
+impl_review_via_prism!([S] ToNever<S>);
+
 #[cfg(feature = "nightly")]
 #[allow(unreachable_code)]
 impl<S> Prism for ToNever<S> {
@@ -224,8 +248,8 @@ impl<S> Prism for ToNever<S> {
 
 /// A `Prism` to "inject" anything into `()`.
 pub struct FromUnit<A, B> {
-    phantom_ua: PhantomData<Fn(()) -> A>,
-    phantom_bu: PhantomData<Fn(B) -> ()>,
+    phantom_ua: PhantomData<fn(()) -> A>,
+    phantom_bu: PhantomData<fn(B) -> ()>,
 }
 
 impl<A, B> FromUnit<A, B> {
@@ -291,15 +315,21 @@ impl<A, B> Lenticuloid for FromUnit<A, B> {
     }
 }
 
+impl_traversal_via_partial_lens!([A, B] FromUnit<A, B>);
+
 impl<A, B> PartialLens for FromUnit<A, B> {
+    type Context = ();
+
     fn try_get(&self, v: ()) -> Result<A, ()> {
         Err(v)
     }
 
-    fn try_get_inject(&self, v: ()) -> Result<(A, Injector<B, ()>), ()> {
+    fn try_get_context(&self, v: ()) -> Result<(A, ()), ()> {
         Err(v)
     }
 
+    fn fill_context(&self, _c: (), _x: B) {}
+
     fn set(&self, v: (), _x: B) -> () {
         v
     }
@@ -321,6 +351,8 @@ impl<A, B> PartialLens for FromUnit<A, B> {
     }
 }
 
+impl_review_via_prism!([A, B] FromUnit<A, B>);
+
 impl<A, B> Prism for FromUnit<A, B> {
     fn inject(&self, _v: B) -> () {
         ()
@@ -329,8 +361,8 @@ impl<A, B> Prism for FromUnit<A, B> {
 
 /// A `Lens` to "extract" `()` from anything.
 pub struct ToUnit<S> {
-    phantom_su: PhantomData<Fn(S) -> ()>,
-    phantom_us: PhantomData<Fn(()) -> S>,
+    phantom_su: PhantomData<fn(S) -> ()>,
+    phantom_us: PhantomData<fn(()) -> S>,
 }
 
 impl<S> ToUnit<S> {
@@ -396,13 +428,21 @@ impl<S> Lenticuloid for ToUnit<S> {
     }
 }
 
+impl_traversal_via_partial_lens!([S] ToUnit<S>);
+
 impl<S> PartialLens for ToUnit<S> {
+    type Context = S;
+
     fn try_get(&self, _v: S) -> Result<(), S> {
         Ok(())
     }
 
-    fn try_get_inject(&self, v: S) -> Result<((), Injector<(), S>), S> {
-        Ok(((), util::once_to_mut(|_| v)))
+    fn try_get_context(&self, v: S) -> Result<((), S), S> {
+        Ok(((), v))
+    }
+
+    fn fill_context(&self, c: S, _x: ()) -> S {
+        c
     }
 
     fn set(&self, v: S, _x: ()) -> S {
@@ -425,6 +465,8 @@ impl<S> PartialLens for ToUnit<S> {
     }
 }
 
+impl_getter_via_lens!([S] ToUnit<S>);
+
 impl<S> Lens for ToUnit<S> {
     fn get(&self, _v: S) -> () {
         ()
@@ -0,0 +1,55 @@
+use super::access::{AsSetter, Setter};
+use super::{Compose, Fold, Identity, Invert, Iso};
+
+/// The supertype of all traversal families.
+pub trait Traversal: Fold + Setter
+    where Self::AtInitial: Traversal,
+          Self::AtFinal: Traversal
+{
+    fn modify_all<F>(&self, v: Self::InitialSource, f: F) -> Self::FinalSource
+        where F: FnMut(Self::InitialTarget) -> Self::FinalTarget
+    {
+        self.over(v, f)
+    }
+
+    /// Forget this traversal's ability to read, leaving only a `Setter`.
+    #[allow(clippy::wrong_self_convention)]
+    fn as_setter(self) -> AsSetter<Self>
+        where Self: Sized
+    {
+        AsSetter::of(self)
+    }
+}
+
+impl<S, T> Traversal for Identity<S, T> {}
+
+impl<LF: Traversal, LS: ?Sized> Traversal for Compose<LF, LS>
+    where LS: Traversal<InitialTarget = LF::InitialSource, FinalTarget = LF::FinalSource>,
+          LF::AtInitial: Traversal,
+          LF::AtFinal: Traversal,
+          LS::AtInitial: Traversal,
+          LS::AtFinal: Traversal
+{
+}
+
+impl<L: Iso> Traversal for Invert<L>
+    where L::AtInitial: Iso,
+          L::AtFinal: Iso
+{
+}
+
+#[cfg(test)]
+mod test {
+    use ::{ComposeExt, Fold, Traversal};
+    use access::Setter;
+    use collections::Index;
+
+    #[test]
+    fn test_compose_traversal() {
+        let l = Index::<Vec<u32>>::of(1).compose(Index::<Vec<Vec<u32>>>::of(0));
+        let v = l.modify_all(vec![vec![1, 2, 3]], |x| x * 10);
+        assert_eq!(v, vec![vec![1, 20, 3]]);
+        assert_eq!(l.to_vec(v), vec![20]);
+        assert!(l.set_all(vec![], 7).is_empty());
+    }
+}